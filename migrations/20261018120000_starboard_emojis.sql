@@ -0,0 +1,13 @@
+CREATE TABLE starboard_emojis(
+    starboard_channel_id BIGINT NOT NULL REFERENCES starboards(channel_id) ON DELETE CASCADE,
+    emoji TEXT NOT NULL,
+    PRIMARY KEY (starboard_channel_id, emoji)
+);
+CREATE INDEX idx_starboard_emojis_emoji ON starboard_emojis(emoji);
+
+INSERT INTO starboard_emojis (starboard_channel_id, emoji)
+SELECT channel_id, emoji FROM starboards;
+
+DROP INDEX idx_starboards_guild_emoji;
+ALTER TABLE starboards DROP COLUMN emoji;
+CREATE INDEX idx_starboards_guild ON starboards(guild_id);
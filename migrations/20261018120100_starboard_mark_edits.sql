@@ -0,0 +1 @@
+ALTER TABLE starboards ADD COLUMN mark_edits BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,2 @@
+ALTER TABLE starboards ADD COLUMN original_delete_policy TEXT NOT NULL DEFAULT 'delete'
+    CHECK (original_delete_policy IN ('delete', 'archive', 'keep'));
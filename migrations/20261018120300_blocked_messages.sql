@@ -0,0 +1,5 @@
+CREATE TABLE blocked_messages(
+    starboard_channel_id BIGINT NOT NULL REFERENCES starboards(channel_id) ON DELETE CASCADE,
+    original_message_id BIGINT NOT NULL,
+    PRIMARY KEY (starboard_channel_id, original_message_id)
+);
@@ -0,0 +1,2 @@
+ALTER TABLE starred_messages ADD COLUMN forced BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE starred_messages ADD COLUMN frozen BOOLEAN NOT NULL DEFAULT FALSE;
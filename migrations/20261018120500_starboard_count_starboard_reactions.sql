@@ -0,0 +1 @@
+ALTER TABLE starboards ADD COLUMN count_starboard_reactions BOOLEAN NOT NULL DEFAULT FALSE;
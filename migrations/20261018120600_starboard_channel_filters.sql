@@ -0,0 +1,6 @@
+CREATE TABLE starboard_channel_filters(
+    starboard_channel_id BIGINT NOT NULL REFERENCES starboards(channel_id) ON DELETE CASCADE,
+    channel_id BIGINT NOT NULL,
+    include BOOLEAN NOT NULL,
+    PRIMARY KEY (starboard_channel_id, channel_id)
+);
@@ -0,0 +1,2 @@
+ALTER TABLE starboards ADD COLUMN visibility_check BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE starboards ADD COLUMN visibility_role_id BIGINT;
@@ -0,0 +1 @@
+ALTER TABLE starboards ADD COLUMN nsfw BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,8 @@
+CREATE TABLE starboard_role_rules(
+    starboard_channel_id BIGINT NOT NULL REFERENCES starboards(channel_id) ON DELETE CASCADE,
+    role_id BIGINT NOT NULL,
+    required BOOLEAN NOT NULL DEFAULT FALSE,
+    excluded BOOLEAN NOT NULL DEFAULT FALSE,
+    weight INTEGER CHECK (weight >= 0),
+    PRIMARY KEY (starboard_channel_id, role_id)
+);
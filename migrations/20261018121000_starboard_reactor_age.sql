@@ -0,0 +1,2 @@
+ALTER TABLE starboards ADD COLUMN min_account_age INTEGER NOT NULL DEFAULT 0 CHECK (min_account_age >= 0);
+ALTER TABLE starboards ADD COLUMN min_member_age INTEGER NOT NULL DEFAULT 0 CHECK (min_member_age >= 0);
@@ -0,0 +1,2 @@
+ALTER TABLE starboards ADD COLUMN veto_emoji TEXT;
+ALTER TABLE starred_messages ADD COLUMN veto_count INTEGER NOT NULL DEFAULT 0 CHECK (veto_count >= 0);
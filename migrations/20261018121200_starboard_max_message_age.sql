@@ -0,0 +1 @@
+ALTER TABLE starboards ADD COLUMN max_message_age INTEGER NOT NULL DEFAULT 0 CHECK (max_message_age >= 0);
@@ -0,0 +1,4 @@
+ALTER TABLE starboards ADD COLUMN parent_starboard_id BIGINT REFERENCES starboards(channel_id) ON DELETE SET NULL;
+ALTER TABLE starboards ADD COLUMN require_parent BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE starboards ADD COLUMN remove_on_promotion BOOLEAN NOT NULL DEFAULT FALSE;
+CREATE INDEX idx_starboards_parent ON starboards(parent_starboard_id);
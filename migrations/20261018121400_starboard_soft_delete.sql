@@ -0,0 +1 @@
+ALTER TABLE starboards ADD COLUMN deleted_at BIGINT;
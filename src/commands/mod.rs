@@ -1,16 +1,127 @@
+mod starboard;
 mod starboard_settings;
 
-pub use self::starboard_settings::starboard_settings_sub;
-use crate::database::DatabasePool;
+pub use self::{
+    starboard::{starboard_context_menu, starboard_diagnose_context_menu, starboard_sub},
+    starboard_settings::starboard_settings_sub,
+};
+use crate::{PoiseContext, database::DatabasePool, emoji::StarboardEmoji};
 use anyhow::Result;
+use poise::serenity_prelude::{MessageId, parse_message_url};
 use sqlx::query;
 
 async fn starboard_setup_in_channel(channel_id: i64, pool: &DatabasePool) -> Result<bool> {
     Ok(query!(
-        "SELECT channel_id FROM starboards WHERE channel_id = ?1",
+        "SELECT channel_id FROM starboards WHERE channel_id = ?1 AND deleted_at IS NULL",
         channel_id,
     )
     .fetch_optional(pool)
     .await?
     .is_some())
 }
+
+/// Check whether a starboard exists in the channel and belongs to the guild the command was used in.
+async fn starboard_in_guild(ctx: PoiseContext<'_>, starboard_channel_id: i64) -> Result<bool> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(false);
+    };
+    let guild_id: i64 = guild_id.get().try_into()?;
+    Ok(query!(
+        "SELECT channel_id FROM starboards WHERE channel_id = ?1 AND guild_id = ?2 AND deleted_at IS NULL",
+        starboard_channel_id,
+        guild_id
+    )
+    .fetch_optional(ctx.data().database.pool())
+    .await?
+    .is_some())
+}
+
+/// Parse an emoji given to a command, returning `None` if it is not a default emoji or one of the guild's custom emojis.
+async fn parse_starboard_emoji(
+    ctx: PoiseContext<'_>,
+    input: &str,
+) -> Result<Option<StarboardEmoji>> {
+    let Some(emoji) = StarboardEmoji::parse(input) else {
+        return Ok(None);
+    };
+    if let StarboardEmoji::Custom { id, .. } = emoji {
+        let Some(guild_id) = ctx.guild_id() else {
+            return Ok(None);
+        };
+        if guild_id.emoji(ctx.http(), id).await.is_err() {
+            return Ok(None);
+        }
+    }
+    Ok(Some(emoji))
+}
+
+/// Parse a message link or ID given to a command.
+fn parse_message_id(input: &str) -> Option<MessageId> {
+    let input = input.trim();
+    if let Some((_, _, message_id)) = parse_message_url(input) {
+        return Some(message_id);
+    }
+    input
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id != 0)
+        .map(MessageId::new)
+}
+
+/// Parse a duration such as `30m`, `12h`, `7d` or `1w2d` given to a command into seconds.
+///
+/// A plain `0` is accepted to disable a duration setting.
+fn parse_duration(input: &str) -> Option<i64> {
+    let input = input.trim();
+    if input == "0" {
+        return Some(0);
+    }
+
+    let mut total: i64 = 0;
+    let mut number = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 60 * 60 * 24,
+            'w' => 60 * 60 * 24 * 7,
+            _ => return None,
+        };
+        let value: i64 = number.parse().ok()?;
+        total = total.checked_add(value.checked_mul(unit)?)?;
+        number.clear();
+    }
+    if !number.is_empty() || total == 0 {
+        return None;
+    }
+    Some(total)
+}
+
+/// Format a duration in seconds for displaying in command responses.
+fn format_duration(seconds: i64) -> String {
+    const UNITS: [(i64, &str); 5] = [
+        (60 * 60 * 24 * 7, "w"),
+        (60 * 60 * 24, "d"),
+        (60 * 60, "h"),
+        (60, "m"),
+        (1, "s"),
+    ];
+
+    let mut remaining = seconds;
+    let mut parts = Vec::new();
+    for (unit, suffix) in UNITS {
+        if remaining >= unit {
+            parts.push(format!("{}{}", remaining / unit, suffix));
+            remaining %= unit;
+        }
+    }
+    match parts.is_empty() {
+        true => "0s".to_string(),
+        false => parts.join(" "),
+    }
+}
@@ -0,0 +1,18 @@
+use super::force_add_entry;
+use crate::{Error, PoiseContext};
+use poise::serenity_prelude::{Channel, Message};
+
+/// Post a message to a starboard regardless of its reactions.
+#[poise::command(rename = "add", prefix_command, slash_command, guild_only)]
+pub async fn add_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to add the message to"]
+    starboard: Channel,
+    #[description = "A link to or the ID of the message"] message: Message,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    let response = force_add_entry(ctx, channel_id, &message).await?;
+    ctx.say(response).await?;
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use super::{force_add_entry, remove_entry, set_entry_frozen};
+use crate::{Error, PoiseContext};
+use poise::{
+    CreateReply,
+    serenity_prelude::{
+        ButtonStyle, ChannelId, ComponentInteractionCollector, ComponentInteractionDataKind,
+        CreateActionRow, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage,
+        CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, Message,
+    },
+};
+use sqlx::query;
+use std::time::Duration;
+
+/// Add, remove or freeze this message on a starboard.
+#[poise::command(
+    context_menu_command = "Moderate starboard entry",
+    guild_only,
+    default_member_permissions = "MANAGE_MESSAGES",
+    required_bot_permissions = "VIEW_CHANNEL | SEND_MESSAGES | READ_MESSAGE_HISTORY |  EMBED_LINKS"
+)]
+pub async fn starboard_context_menu(ctx: PoiseContext<'_>, message: Message) -> Result<(), Error> {
+    let guild_id: i64 = match ctx.guild_id() {
+        Some(g) => g.get().try_into()?,
+        None => return Ok(()),
+    };
+    let starboards = query!(
+        "SELECT channel_id FROM starboards WHERE guild_id = ?1 AND deleted_at IS NULL ORDER BY channel_id",
+        guild_id
+    )
+    .fetch_all(ctx.data().database.pool())
+    .await?;
+    if starboards.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content("There are no starboards in this server.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // Build a starboard picker and a button for each action.
+    let mut options = Vec::with_capacity(starboards.len());
+    for starboard in starboards.iter().take(25) {
+        let channel = ChannelId::new(starboard.channel_id.try_into()?);
+        let name = channel
+            .name(ctx)
+            .await
+            .unwrap_or_else(|_| channel.to_string());
+        options.push(CreateSelectMenuOption::new(
+            format!("#{name}"),
+            starboard.channel_id.to_string(),
+        ));
+    }
+    let id = ctx.id();
+    let select_id = format!("{id}-starboard");
+    let add_id = format!("{id}-add");
+    let remove_id = format!("{id}-remove");
+    let freeze_id = format!("{id}-freeze");
+    let unfreeze_id = format!("{id}-unfreeze");
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .content("Select a starboard and an action for this message.")
+                .ephemeral(true)
+                .components(vec![
+                    CreateActionRow::SelectMenu(
+                        CreateSelectMenu::new(&select_id, CreateSelectMenuKind::String { options })
+                            .placeholder("Starboard"),
+                    ),
+                    CreateActionRow::Buttons(vec![
+                        CreateButton::new(&add_id)
+                            .style(ButtonStyle::Success)
+                            .label("Force add"),
+                        CreateButton::new(&remove_id)
+                            .style(ButtonStyle::Danger)
+                            .label("Remove"),
+                        CreateButton::new(&freeze_id)
+                            .style(ButtonStyle::Secondary)
+                            .label("Freeze"),
+                        CreateButton::new(&unfreeze_id)
+                            .style(ButtonStyle::Secondary)
+                            .label("Unfreeze"),
+                    ]),
+                ]),
+        )
+        .await?;
+
+    // Only preselect the starboard when there is no choice to make.
+    let mut selected_starboard = match starboards.as_slice() {
+        [starboard] => Some(starboard.channel_id),
+        _ => None,
+    };
+    while let Some(interaction) = ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .timeout(Duration::from_secs(120))
+        .filter(move |i| i.data.custom_id.starts_with(&id.to_string()))
+        .await
+    {
+        if interaction.data.custom_id == select_id {
+            if let ComponentInteractionDataKind::StringSelect { values } = &interaction.data.kind {
+                selected_starboard = values.first().and_then(|v| v.parse().ok());
+            }
+            interaction
+                .create_response(ctx, CreateInteractionResponse::Acknowledge)
+                .await?;
+            continue;
+        }
+
+        let Some(starboard_channel_id) = selected_starboard else {
+            interaction
+                .create_response(
+                    ctx,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content("Select a starboard first.")
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+            continue;
+        };
+        interaction
+            .create_response(ctx, CreateInteractionResponse::Acknowledge)
+            .await?;
+
+        let response = match interaction.data.custom_id.as_str() {
+            custom_id if custom_id == add_id => {
+                force_add_entry(ctx, starboard_channel_id, &message).await?
+            }
+            custom_id if custom_id == remove_id => {
+                remove_entry(ctx, starboard_channel_id, message.id).await?
+            }
+            custom_id if custom_id == freeze_id => {
+                set_entry_frozen(ctx, starboard_channel_id, message.id, true).await?
+            }
+            custom_id if custom_id == unfreeze_id => {
+                set_entry_frozen(ctx, starboard_channel_id, message.id, false).await?
+            }
+            _ => continue,
+        };
+        reply
+            .edit(
+                ctx,
+                CreateReply::default().content(response).components(vec![]),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    // Remove the components once the interaction has expired.
+    reply
+        .edit(
+            ctx,
+            CreateReply::default()
+                .content("This interaction has expired.")
+                .components(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
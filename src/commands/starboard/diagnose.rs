@@ -0,0 +1,113 @@
+use crate::{
+    Error, PoiseContext,
+    events::{Verdict, evaluate_entry},
+};
+use poise::{CreateReply, serenity_prelude::Message};
+use sqlx::query;
+
+/// Explain how this message stands on each starboard in the server.
+#[poise::command(
+    context_menu_command = "Why isn't this starred?",
+    guild_only,
+    user_cooldown = 10,
+    required_bot_permissions = "VIEW_CHANNEL | SEND_MESSAGES | READ_MESSAGE_HISTORY"
+)]
+pub async fn starboard_diagnose_context_menu(
+    ctx: PoiseContext<'_>,
+    message: Message,
+) -> Result<(), Error> {
+    let Some(guild) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let guild_id: i64 = guild.get().try_into()?;
+    ctx.defer_ephemeral().await?;
+
+    let pool = ctx.data().database.pool();
+    let starboards = query!(
+        "SELECT channel_id FROM starboards WHERE guild_id = ?1 AND deleted_at IS NULL ORDER BY channel_id",
+        guild_id
+    )
+    .fetch_all(pool)
+    .await?;
+    if starboards.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content("There are no starboards in this server.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // Run the same checks as reactions do, without posting anything.
+    let mut lines = Vec::with_capacity(starboards.len());
+    for starboard in starboards {
+        if i64::try_from(message.channel_id.get())? == starboard.channel_id {
+            lines.push(format!(
+                "<#{}>: this message is inside of the starboard.",
+                starboard.channel_id
+            ));
+            continue;
+        }
+        let evaluation = evaluate_entry(
+            ctx.serenity_context(),
+            pool,
+            starboard.channel_id,
+            &message,
+            false,
+        )
+        .await?;
+        let mut line = match evaluation.verdict {
+            Verdict::Skipped(reason) => {
+                format!("<#{}>: not counted - {}.", starboard.channel_id, reason)
+            }
+            Verdict::Absent {
+                react_count,
+                threshold,
+                estimated: true,
+            } => format!(
+                "<#{}>: not enough reactions - at most **{}** of **{}** needed.",
+                starboard.channel_id, react_count, threshold
+            ),
+            Verdict::Absent {
+                react_count,
+                threshold,
+                estimated: false,
+            } => format!(
+                "<#{}>: not enough reactions - **{}** of **{}** needed.",
+                starboard.channel_id, react_count, threshold
+            ),
+            Verdict::Present {
+                react_count,
+                threshold,
+            } => match evaluation.starboard_message_id {
+                Some(starboard_message_id) => format!(
+                    "<#{}>: already posted with **{}** of **{}** needed - https://discord.com/channels/{}/{}/{}",
+                    starboard.channel_id,
+                    react_count,
+                    threshold,
+                    guild_id,
+                    starboard.channel_id,
+                    starboard_message_id
+                ),
+                None => format!(
+                    "<#{}>: has **{}** of **{}** needed and will be posted on the next reaction.",
+                    starboard.channel_id, react_count, threshold
+                ),
+            },
+        };
+        if evaluation.selfstar_excluded {
+            line.push_str(" The author's own reaction is not counted.");
+        }
+        lines.push(line);
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .content(lines.join("\n"))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use super::set_entry_frozen;
+use crate::{Error, PoiseContext, commands::parse_message_id};
+use poise::serenity_prelude::Channel;
+
+/// Freeze a starboard entry so its count and content stop updating.
+#[poise::command(rename = "freeze", prefix_command, slash_command, guild_only)]
+pub async fn freeze_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard the entry is on"]
+    starboard: Channel,
+    #[description = "A link to or the ID of the original message"] message: String,
+    #[description = "Whether the entry should be frozen"] frozen: bool,
+) -> Result<(), Error> {
+    let Some(message_id) = parse_message_id(&message) else {
+        ctx.say("Invalid message. Provide a message link or ID.")
+            .await?;
+        return Ok(());
+    };
+    let channel_id = starboard.id().get().try_into()?;
+    let response = set_entry_frozen(ctx, channel_id, message_id, frozen).await?;
+    ctx.say(response).await?;
+    Ok(())
+}
@@ -0,0 +1,194 @@
+mod add;
+mod context_menu;
+mod diagnose;
+mod freeze;
+mod remove;
+
+use self::{add::add_cmd, freeze::freeze_cmd, remove::remove_cmd};
+pub use self::{context_menu::starboard_context_menu, diagnose::starboard_diagnose_context_menu};
+use crate::{
+    PoiseContext,
+    commands::starboard_in_guild,
+    events::{Verdict, delete_entry, reconcile_entry},
+};
+use anyhow::Result;
+use poise::serenity_prelude::{Message, MessageId};
+use sqlx::query;
+
+/// A collection of commands for moderating starboard entries.
+#[poise::command(
+    rename = "starboard",
+    prefix_command,
+    slash_command,
+    hide_in_help,
+    default_member_permissions = "MANAGE_MESSAGES",
+    required_permissions = "MANAGE_MESSAGES",
+    required_bot_permissions = "VIEW_CHANNEL | SEND_MESSAGES | READ_MESSAGE_HISTORY |  EMBED_LINKS",
+    guild_cooldown = 5s,
+    subcommand_required,
+    subcommands("add_cmd", "remove_cmd", "freeze_cmd")
+)]
+pub async fn starboard_sub(_: PoiseContext<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Post a message to a starboard regardless of its reactions, returning a response for the user.
+async fn force_add_entry(
+    ctx: PoiseContext<'_>,
+    starboard_channel_id: i64,
+    message: &Message,
+) -> Result<String> {
+    let pool = ctx.data().database.pool();
+    if !starboard_in_guild(ctx, starboard_channel_id).await? {
+        return Ok("A starboard does not exist for that channel.".into());
+    }
+
+    // Forcing skips the visibility checks, so messages from other guilds must never be posted.
+    let message_guild_id = match message.guild_id {
+        Some(guild_id) => Some(guild_id),
+        None => message
+            .channel_id
+            .to_channel(ctx)
+            .await?
+            .guild()
+            .map(|channel| channel.guild_id),
+    };
+    if message_guild_id.is_none() || message_guild_id != ctx.guild_id() {
+        return Ok("Only messages from this server can be added to its starboards.".into());
+    }
+    if i64::try_from(message.channel_id.get())? == starboard_channel_id {
+        return Ok("Messages inside of a starboard can't be added to it.".into());
+    }
+    let message_id: i64 = message.id.get().try_into()?;
+
+    // Adding a message manually lifts any previous removal.
+    query!(
+        "DELETE FROM blocked_messages WHERE starboard_channel_id = ?1 AND original_message_id = ?2",
+        starboard_channel_id,
+        message_id
+    )
+    .execute(pool)
+    .await?;
+
+    match reconcile_entry(
+        ctx.serenity_context(),
+        ctx.data(),
+        starboard_channel_id,
+        message,
+        true,
+    )
+    .await?
+    {
+        Verdict::Present { .. } => Ok(format!(
+            "Added the message to the starboard in <#{}>. It will stay there regardless of its reactions.",
+            starboard_channel_id
+        )),
+        Verdict::Skipped(reason) => Ok(format!(
+            "The message could not be added to the starboard in <#{}>: {}.",
+            starboard_channel_id, reason
+        )),
+        Verdict::Absent { .. } => Ok(format!(
+            "The message could not be added to the starboard in <#{}>.",
+            starboard_channel_id
+        )),
+    }
+}
+
+/// Remove a message from a starboard and prevent it from being posted again, returning a response for the user.
+async fn remove_entry(
+    ctx: PoiseContext<'_>,
+    starboard_channel_id: i64,
+    message_id: MessageId,
+) -> Result<String> {
+    let pool = ctx.data().database.pool();
+    if !starboard_in_guild(ctx, starboard_channel_id).await? {
+        return Ok("A starboard does not exist for that channel.".into());
+    }
+    let message_id: i64 = message_id.get().try_into()?;
+
+    // Hold the entry so a reaction being handled can't post the message after it is blocked.
+    let _entry_lock = ctx
+        .data()
+        .entry_locks
+        .lock(starboard_channel_id, message_id)
+        .await;
+    let entry = query!(
+        "SELECT starboard_message_id FROM starred_messages
+        WHERE starboard_channel_id = ?1 AND original_message_id = ?2",
+        starboard_channel_id,
+        message_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    query!(
+        "INSERT OR IGNORE INTO blocked_messages (starboard_channel_id, original_message_id) VALUES (?1, ?2)",
+        starboard_channel_id,
+        message_id
+    )
+    .execute(pool)
+    .await?;
+
+    let Some(entry) = entry else {
+        return Ok(format!(
+            "That message is not on the starboard in <#{}>, but it has been blocked from being posted there.",
+            starboard_channel_id
+        ));
+    };
+
+    delete_entry(
+        ctx.serenity_context(),
+        pool,
+        starboard_channel_id,
+        entry.starboard_message_id,
+    )
+    .await?;
+
+    Ok(format!(
+        "Removed the message from the starboard in <#{}>. It will not be posted there again unless unblocked.",
+        starboard_channel_id
+    ))
+}
+
+/// Freeze or unfreeze a starboard entry, returning a response for the user.
+async fn set_entry_frozen(
+    ctx: PoiseContext<'_>,
+    starboard_channel_id: i64,
+    message_id: MessageId,
+    frozen: bool,
+) -> Result<String> {
+    if !starboard_in_guild(ctx, starboard_channel_id).await? {
+        return Ok("A starboard does not exist for that channel.".into());
+    }
+    let message_id: i64 = message_id.get().try_into()?;
+    let _entry_lock = ctx
+        .data()
+        .entry_locks
+        .lock(starboard_channel_id, message_id)
+        .await;
+    let result = query!(
+        "UPDATE starred_messages SET frozen = ?1 WHERE starboard_channel_id = ?2 AND original_message_id = ?3",
+        frozen,
+        starboard_channel_id,
+        message_id
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+    if result.rows_affected() == 0 {
+        return Ok(format!(
+            "That message is not on the starboard in <#{}>.",
+            starboard_channel_id
+        ));
+    }
+
+    Ok(match frozen {
+        true => format!(
+            "Froze the entry on the starboard in <#{}> - it will no longer be updated.",
+            starboard_channel_id
+        ),
+        false => format!(
+            "Unfroze the entry on the starboard in <#{}>.",
+            starboard_channel_id
+        ),
+    })
+}
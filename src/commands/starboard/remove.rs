@@ -0,0 +1,23 @@
+use super::remove_entry;
+use crate::{Error, PoiseContext, commands::parse_message_id};
+use poise::serenity_prelude::Channel;
+
+/// Remove a message from a starboard and prevent it from being posted again.
+#[poise::command(rename = "remove", prefix_command, slash_command, guild_only)]
+pub async fn remove_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to remove the message from"]
+    starboard: Channel,
+    #[description = "A link to or the ID of the original message"] message: String,
+) -> Result<(), Error> {
+    let Some(message_id) = parse_message_id(&message) else {
+        ctx.say("Invalid message. Provide a message link or ID.")
+            .await?;
+        return Ok(());
+    };
+    let channel_id = starboard.id().get().try_into()?;
+    let response = remove_entry(ctx, channel_id, message_id).await?;
+    ctx.say(response).await?;
+    Ok(())
+}
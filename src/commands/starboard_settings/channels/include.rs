@@ -0,0 +1,42 @@
+use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
+use poise::serenity_prelude::{Channel, Mentionable};
+use sqlx::query;
+
+/// Only allow messages from a channel or category to be posted to a starboard.
+#[poise::command(rename = "include", prefix_command, slash_command, guild_only)]
+pub async fn include_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[channel_types("Text", "News", "Forum", "Category")]
+    #[description = "The channel or category to include"]
+    channel: Channel,
+) -> Result<(), Error> {
+    let starboard_channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(starboard_channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let channel_id: i64 = channel.id().get().try_into()?;
+    query!(
+        "INSERT INTO starboard_channel_filters (starboard_channel_id, channel_id, include) VALUES (?1, ?2, ?3)
+        ON CONFLICT (starboard_channel_id, channel_id) DO UPDATE SET include = ?3",
+        starboard_channel_id,
+        channel_id,
+        true,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+
+    ctx.say(format!(
+        "Messages from {} can now be posted to the starboard in {}. Only included channels will be used.",
+        channel.mention(),
+        starboard.mention()
+    ))
+    .await?;
+
+    Ok(())
+}
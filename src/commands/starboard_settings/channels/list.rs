@@ -0,0 +1,55 @@
+use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
+use poise::serenity_prelude::{Channel, Mentionable};
+use sqlx::query;
+
+/// List the channels and categories included or excluded for a starboard.
+#[poise::command(rename = "list", prefix_command, slash_command, guild_only)]
+pub async fn list_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to view"]
+    starboard: Channel,
+) -> Result<(), Error> {
+    let starboard_channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(starboard_channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let filters = query!(
+        "SELECT channel_id, include FROM starboard_channel_filters WHERE starboard_channel_id = ?1",
+        starboard_channel_id
+    )
+    .fetch_all(ctx.data().database.pool())
+    .await?;
+    if filters.is_empty() {
+        ctx.say(format!(
+            "The starboard in {} accepts messages from every channel.",
+            starboard.mention()
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let format_channels = |include: bool| {
+        let channels = filters
+            .iter()
+            .filter(|f| f.include == include)
+            .map(|f| format!("<#{}>", f.channel_id))
+            .collect::<Vec<_>>();
+        match channels.is_empty() {
+            true => "*None*".to_string(),
+            false => channels.join(" "),
+        }
+    };
+    ctx.say(format!(
+        "Channel rules for the starboard in {}:\n**Included:** {}\n**Excluded:** {}",
+        starboard.mention(),
+        format_channels(true),
+        format_channels(false)
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+mod exclude;
+mod include;
+mod list;
+mod reset;
+
+use self::{exclude::exclude_cmd, include::include_cmd, list::list_cmd, reset::reset_cmd};
+use crate::PoiseContext;
+use anyhow::Result;
+
+/// Manage which channels can feed a starboard.
+#[poise::command(
+    rename = "channels",
+    prefix_command,
+    slash_command,
+    guild_only,
+    subcommand_required,
+    subcommands("include_cmd", "exclude_cmd", "reset_cmd", "list_cmd")
+)]
+pub async fn channels_cmd(_: PoiseContext<'_>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
+use poise::serenity_prelude::{Channel, Mentionable};
+use sqlx::query;
+
+/// Remove the include or exclude rule for a channel or category on a starboard.
+#[poise::command(rename = "reset", prefix_command, slash_command, guild_only)]
+pub async fn reset_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[channel_types("Text", "News", "Forum", "Category")]
+    #[description = "The channel or category to reset"]
+    channel: Channel,
+) -> Result<(), Error> {
+    let starboard_channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(starboard_channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let channel_id: i64 = channel.id().get().try_into()?;
+    let result = query!(
+        "DELETE FROM starboard_channel_filters WHERE starboard_channel_id = ?1 AND channel_id = ?2",
+        starboard_channel_id,
+        channel_id,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+    if result.rows_affected() == 0 {
+        ctx.say(format!(
+            "{} is not included or excluded for the starboard in {}.",
+            channel.mention(),
+            starboard.mention()
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    ctx.say(format!(
+        "Removed the rule for {} from the starboard in {}.",
+        channel.mention(),
+        starboard.mention()
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
+use poise::serenity_prelude::Channel;
+use sqlx::query;
+
+/// Change whether reactions on starboard messages count towards the original message.
+#[poise::command(
+    rename = "count-starboard-reactions",
+    prefix_command,
+    slash_command,
+    guild_only
+)]
+pub async fn count_starboard_reactions_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "Count reactions on the starboard message"] count_starboard_reactions: bool,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    query!(
+        "UPDATE starboards SET count_starboard_reactions = ?1 WHERE channel_id = ?2",
+        count_starboard_reactions,
+        channel_id,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+
+    ctx.say(format!(
+        "Updated starboard setting 'count starboard reactions' to **{}**.",
+        count_starboard_reactions
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
+use crate::{
+    Error, PoiseContext,
+    commands::{parse_starboard_emoji, starboard_setup_in_channel},
+};
 use poise::serenity_prelude::Channel;
 use sqlx::query;
 
@@ -24,8 +27,8 @@ pub async fn create_cmd(
     };
 
     // Ensure the given emoji is valid.
-    if emojis::get(&emoji).is_none() {
-        ctx.say("Invalid or unknown emoji. You can only use Discord's default emojis for the starboard.")
+    let Some(emoji) = parse_starboard_emoji(ctx, &emoji).await? else {
+        ctx.say("Invalid or unknown emoji. You can only use Discord's default emojis or this server's custom emojis for the starboard.")
             .await?;
         return Ok(());
     };
@@ -37,20 +40,37 @@ pub async fn create_cmd(
         return Ok(());
     }
 
+    // A deleted starboard waiting to be purged is replaced by the new one.
+    query!(
+        "DELETE FROM starboards WHERE channel_id = ?1 AND deleted_at IS NOT NULL",
+        channel_id
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+
     // Create starboard.
     let allow_selfstar = allow_selfstar.unwrap_or(false);
+    let nsfw = channel.guild().is_some_and(|c| c.nsfw);
+    let stored_emoji = emoji.to_stored();
     query!("INSERT OR IGNORE INTO guilds (id) VALUES (?1)", guild_id)
         .execute(ctx.data().database.pool())
         .await?;
     query!(
-        "INSERT INTO starboards (guild_id, enabled, channel_id, emoji, threshold, allow_selfstar) 
+        "INSERT INTO starboards (guild_id, enabled, channel_id, threshold, allow_selfstar, nsfw) 
         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
         guild_id,
         true,
         channel_id,
-        emoji,
         threshold,
-        allow_selfstar
+        allow_selfstar,
+        nsfw
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+    query!(
+        "INSERT INTO starboard_emojis (starboard_channel_id, emoji) VALUES (?1, ?2)",
+        channel_id,
+        stored_emoji
     )
     .execute(ctx.data().database.pool())
     .await?;
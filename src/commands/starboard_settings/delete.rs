@@ -1,8 +1,19 @@
-use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
-use poise::serenity_prelude::Channel;
+use crate::{
+    Error, PoiseContext,
+    commands::{format_duration, starboard_setup_in_channel},
+    starboard_purge::RESTORE_WINDOW,
+};
+use poise::{
+    CreateReply,
+    serenity_prelude::{
+        ButtonStyle, Channel, ComponentInteractionCollector, CreateActionRow, CreateButton,
+        CreateInteractionResponse, Mentionable, Timestamp,
+    },
+};
 use sqlx::query;
+use std::time::Duration;
 
-/// DANGER: Delete a starboard. Breaks all ties between a starboard message & original message.
+/// DANGER: Delete a starboard. It can be restored for a week before its entries are removed for good.
 #[poise::command(rename = "delete", prefix_command, slash_command, guild_only)]
 pub async fn delete_cmd(
     ctx: PoiseContext<'_>,
@@ -10,19 +21,82 @@ pub async fn delete_cmd(
     #[description = "The starboard to delete"]
     starboard: Channel,
 ) -> Result<(), Error> {
-    let channel_id = starboard.id().get().try_into()?;
+    let channel_id: i64 = starboard.id().get().try_into()?;
     if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
         ctx.say("A starboard does not exist for that channel.")
             .await?;
         return Ok(());
     }
 
-    // TODO: interaction with warning about data loss.
+    // Warn about what will be lost before deleting anything.
+    let entry_count = query!(
+        r#"SELECT COUNT(*) AS "count!: i64" FROM starred_messages WHERE starboard_channel_id = ?1"#,
+        channel_id
+    )
+    .fetch_one(ctx.data().database.pool())
+    .await?
+    .count;
+    let id = ctx.id();
+    let confirm_id = format!("{id}-confirm");
+    let cancel_id = format!("{id}-cancel");
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .content(format!(
+                    "Deleting the starboard in {} will stop it from tracking **{}** posted {}. It can be restored with `starboard-settings restore` for **{}**, after which it and its entries are permanently removed.",
+                    starboard.mention(),
+                    entry_count,
+                    if entry_count == 1 { "message" } else { "messages" },
+                    format_duration(RESTORE_WINDOW)
+                ))
+                .components(vec![CreateActionRow::Buttons(vec![
+                    CreateButton::new(&confirm_id)
+                        .style(ButtonStyle::Danger)
+                        .label("Delete"),
+                    CreateButton::new(&cancel_id)
+                        .style(ButtonStyle::Secondary)
+                        .label("Cancel"),
+                ])]),
+        )
+        .await?;
 
-    query!("DELETE FROM starboards WHERE channel_id = ?1", channel_id)
-        .execute(ctx.data().database.pool())
+    let interaction = ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .timeout(Duration::from_secs(60))
+        .filter(move |i| i.data.custom_id.starts_with(&id.to_string()))
+        .await;
+    let response = match interaction {
+        Some(interaction) => {
+            interaction
+                .create_response(ctx, CreateInteractionResponse::Acknowledge)
+                .await?;
+            if interaction.data.custom_id == confirm_id {
+                let now = Timestamp::now().unix_timestamp();
+                query!(
+                    "UPDATE starboards SET deleted_at = ?1 WHERE channel_id = ?2",
+                    now,
+                    channel_id
+                )
+                .execute(ctx.data().database.pool())
+                .await?;
+                format!(
+                    "The starboard in {} has been deleted. Use `starboard-settings restore` within **{}** to bring it back.",
+                    starboard.mention(),
+                    format_duration(RESTORE_WINDOW)
+                )
+            } else {
+                "Cancelled deleting the starboard.".to_string()
+            }
+        }
+        None => "This interaction has expired.".to_string(),
+    };
+    reply
+        .edit(
+            ctx,
+            CreateReply::default().content(response).components(vec![]),
+        )
         .await?;
-    ctx.say("The starboard in that channel has been deleted successfully and all recorded messages have been removed from storage.").await?;
 
     Ok(())
 }
@@ -0,0 +1,78 @@
+use super::super::reevaluate::reevaluate_starboard;
+use crate::{
+    Error, PoiseContext,
+    commands::{parse_starboard_emoji, starboard_setup_in_channel},
+};
+use poise::serenity_prelude::Channel;
+use sqlx::query;
+
+/// Add a 'star' emoji to a starboard.
+#[poise::command(rename = "add", prefix_command, slash_command, guild_only)]
+pub async fn emoji_add_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "The emoji to add as a 'star'"] emoji: String,
+) -> Result<(), Error> {
+    let channel_id: i64 = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let Some(emoji) = parse_starboard_emoji(ctx, &emoji).await? else {
+        ctx.say("Invalid or unknown emoji. You can only use Discord's default emojis or this server's custom emojis for the starboard.")
+            .await?;
+        return Ok(());
+    };
+
+    let stored_emoji = emoji.to_stored();
+    if query!(
+        "SELECT channel_id FROM starboards WHERE channel_id = ?1 AND veto_emoji = ?2",
+        channel_id,
+        stored_emoji,
+    )
+    .fetch_optional(ctx.data().database.pool())
+    .await?
+    .is_some()
+    {
+        ctx.say(format!(
+            "{} is the veto emoji for that starboard and can't also be a 'star'.",
+            emoji
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let result = query!(
+        "INSERT OR IGNORE INTO starboard_emojis (starboard_channel_id, emoji) VALUES (?1, ?2)",
+        channel_id,
+        stored_emoji,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+    if result.rows_affected() == 0 {
+        ctx.say(format!("{} is already a 'star' for that starboard.", emoji))
+            .await?;
+        return Ok(());
+    }
+
+    ctx.say(format!("Added {} as a 'star' for the starboard.", emoji))
+        .await?;
+
+    // Existing entries may have more reactions with the new emoji.
+    if query!(
+        "SELECT original_message_id FROM starred_messages WHERE starboard_channel_id = ?1 LIMIT 1",
+        channel_id
+    )
+    .fetch_optional(ctx.data().database.pool())
+    .await?
+    .is_some()
+    {
+        reevaluate_starboard(ctx, channel_id, None).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::{Error, PoiseContext, commands::starboard_setup_in_channel, emoji::StarboardEmoji};
+use poise::serenity_prelude::{Channel, Mentionable};
+use sqlx::query;
+
+/// List the 'star' emojis for a starboard.
+#[poise::command(rename = "list", prefix_command, slash_command, guild_only)]
+pub async fn emoji_list_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to view"]
+    starboard: Channel,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let emojis = query!(
+        "SELECT emoji FROM starboard_emojis WHERE starboard_channel_id = ?1 ORDER BY rowid",
+        channel_id
+    )
+    .fetch_all(ctx.data().database.pool())
+    .await?
+    .into_iter()
+    .map(|row| StarboardEmoji::from_stored(&row.emoji).to_string())
+    .collect::<Vec<_>>();
+
+    ctx.say(format!(
+        "The starboard in {} counts reactions from: {}",
+        starboard.mention(),
+        emojis.join(" ")
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+mod add;
+mod list;
+mod remove;
+
+use self::{add::emoji_add_cmd, list::emoji_list_cmd, remove::emoji_remove_cmd};
+use crate::PoiseContext;
+use anyhow::Result;
+
+/// Manage the 'star' emojis for a starboard.
+#[poise::command(
+    rename = "emoji",
+    prefix_command,
+    slash_command,
+    guild_only,
+    subcommand_required,
+    subcommands("emoji_add_cmd", "emoji_remove_cmd", "emoji_list_cmd")
+)]
+pub async fn emoji_cmd(_: PoiseContext<'_>) -> Result<()> {
+    Ok(())
+}
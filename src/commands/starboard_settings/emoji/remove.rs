@@ -0,0 +1,195 @@
+use super::super::reevaluate::reevaluate_starboard;
+use crate::{
+    Error, PoiseContext,
+    commands::starboard_setup_in_channel,
+    emoji::StarboardEmoji,
+    events::{Verdict, evaluate_entry_with_emojis},
+};
+use poise::{
+    CreateReply,
+    serenity_prelude::{
+        ButtonStyle, Channel, ChannelId, ComponentInteractionCollector, CreateActionRow,
+        CreateButton, CreateInteractionResponse, MessageId,
+    },
+};
+use sqlx::query;
+use std::time::Duration;
+use tracing::warn;
+
+/// Remove a 'star' emoji from a starboard.
+#[poise::command(rename = "remove", prefix_command, slash_command, guild_only)]
+pub async fn emoji_remove_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "The 'star' emoji to remove"] emoji: String,
+) -> Result<(), Error> {
+    let channel_id: i64 = starboard.id().get().try_into()?;
+    let pool = ctx.data().database.pool();
+    if !starboard_setup_in_channel(channel_id, pool).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    // Custom emojis that were deleted from the guild should still be removable.
+    let Some(emoji) = StarboardEmoji::parse(&emoji) else {
+        ctx.say("Invalid or unknown emoji.").await?;
+        return Ok(());
+    };
+    let stored_emoji = emoji.to_stored();
+
+    let emojis = query!(
+        "SELECT emoji FROM starboard_emojis WHERE starboard_channel_id = ?1 ORDER BY rowid",
+        channel_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| StarboardEmoji::from_stored(&row.emoji))
+    .collect::<Vec<_>>();
+    if !emojis.contains(&emoji) {
+        ctx.say(format!("{} is not a 'star' for that starboard.", emoji))
+            .await?;
+        return Ok(());
+    }
+    if emojis.len() <= 1 {
+        ctx.say("A starboard must have at least one 'star' emoji. Add another emoji before removing this one.")
+            .await?;
+        return Ok(());
+    }
+    let remaining_emojis = emojis
+        .into_iter()
+        .filter(|e| *e != emoji)
+        .collect::<Vec<_>>();
+
+    // Recount the existing entries without the emoji before committing to the change.
+    let entries = query!(
+        "SELECT original_message_id, original_message_channel_id FROM starred_messages
+        WHERE starboard_channel_id = ?1 AND frozen = FALSE AND forced = FALSE",
+        channel_id
+    )
+    .fetch_all(pool)
+    .await?;
+    if !entries.is_empty() {
+        let reply = ctx
+            .say(format!(
+                "Checking how removing {} affects {} entries...",
+                emoji,
+                entries.len()
+            ))
+            .await?;
+        let mut below_threshold = 0;
+        let mut failed = 0;
+        for entry in &entries {
+            // One entry that can't be checked shouldn't stop the others from being previewed.
+            let message = match ChannelId::new(entry.original_message_channel_id.try_into()?)
+                .message(ctx, MessageId::new(entry.original_message_id.try_into()?))
+                .await
+            {
+                Ok(message) => message,
+                Err(err) => {
+                    warn!(
+                        original_message_id = entry.original_message_id,
+                        "Failed to get original message for emoji removal preview: {err:?}"
+                    );
+                    failed += 1;
+                    continue;
+                }
+            };
+            match evaluate_entry_with_emojis(
+                ctx.serenity_context(),
+                pool,
+                channel_id,
+                &message,
+                &remaining_emojis,
+            )
+            .await
+            {
+                Ok(evaluation) => {
+                    if matches!(evaluation.verdict, Verdict::Absent { .. }) {
+                        below_threshold += 1;
+                    }
+                }
+                Err(err) => {
+                    warn!(
+                        message_id = message.id.get(),
+                        "Failed to preview starboard entry without emoji: {err:?}"
+                    );
+                    failed += 1;
+                }
+            }
+        }
+
+        let id = ctx.id();
+        let confirm_id = format!("{id}-confirm");
+        let keep_id = format!("{id}-keep");
+        reply
+            .edit(
+                ctx,
+                CreateReply::default()
+                    .content(format!(
+                        "Without {}, **{}** of {} entries would fall below the threshold and be removed from the starboard. The rest will be recounted and updated.{}",
+                        emoji,
+                        below_threshold,
+                        entries.len(),
+                        if failed > 0 {
+                            format!(" **{}** entries couldn't be checked.", failed)
+                        } else {
+                            String::new()
+                        }
+                    ))
+                    .components(vec![CreateActionRow::Buttons(vec![
+                        CreateButton::new(&confirm_id)
+                            .style(ButtonStyle::Danger)
+                            .label("Remove and recount"),
+                        CreateButton::new(&keep_id)
+                            .style(ButtonStyle::Secondary)
+                            .label("Keep as a 'star'"),
+                    ])]),
+            )
+            .await?;
+        let interaction = ComponentInteractionCollector::new(ctx)
+            .author_id(ctx.author().id)
+            .channel_id(ctx.channel_id())
+            .timeout(Duration::from_secs(60))
+            .filter(move |i| i.data.custom_id.starts_with(&id.to_string()))
+            .await;
+        if let Some(interaction) = &interaction {
+            interaction
+                .create_response(ctx, CreateInteractionResponse::Acknowledge)
+                .await?;
+        }
+        if interaction.is_none_or(|i| i.data.custom_id != confirm_id) {
+            reply
+                .edit(
+                    ctx,
+                    CreateReply::default()
+                        .content(format!("Kept {} as a 'star' for the starboard.", emoji))
+                        .components(vec![]),
+                )
+                .await?;
+            return Ok(());
+        }
+        reply
+            .edit(ctx, CreateReply::default().components(vec![]))
+            .await?;
+    }
+
+    query!(
+        "DELETE FROM starboard_emojis WHERE starboard_channel_id = ?1 AND emoji = ?2",
+        channel_id,
+        stored_emoji,
+    )
+    .execute(pool)
+    .await?;
+    ctx.say(format!("Removed {} as a 'star' for the starboard.", emoji))
+        .await?;
+
+    if !entries.is_empty() {
+        reevaluate_starboard(ctx, channel_id, None).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::{Error, PoiseContext, emoji::StarboardEmoji};
+use poise::{
+    CreateReply,
+    serenity_prelude::{Colour, CreateEmbed},
+};
+use sqlx::query;
+
+/// List the starboards in this server.
+#[poise::command(rename = "list", prefix_command, slash_command, guild_only)]
+pub async fn list_cmd(ctx: PoiseContext<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let guild_id: i64 = guild_id.get().try_into()?;
+    let pool = ctx.data().database.pool();
+
+    let starboards = query!(
+        r#"SELECT s.channel_id, s.enabled, s.threshold, s.allow_selfstar,
+            (SELECT COUNT(*) FROM starred_messages sm WHERE sm.starboard_channel_id = s.channel_id) AS "entry_count!: i64"
+        FROM starboards s WHERE s.guild_id = ?1 AND s.deleted_at IS NULL ORDER BY s.channel_id"#,
+        guild_id
+    )
+    .fetch_all(pool)
+    .await?;
+    if starboards.is_empty() {
+        ctx.say("There are no starboards in this server.").await?;
+        return Ok(());
+    }
+    let emojis = query!(
+        "SELECT starboard_channel_id, emoji FROM starboard_emojis
+        WHERE starboard_channel_id IN (SELECT channel_id FROM starboards WHERE guild_id = ?1 AND deleted_at IS NULL)
+        ORDER BY rowid",
+        guild_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let lines = starboards
+        .iter()
+        .map(|starboard| {
+            let starboard_emojis = emojis
+                .iter()
+                .filter(|e| e.starboard_channel_id == starboard.channel_id)
+                .map(|e| StarboardEmoji::from_stored(&e.emoji).to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!(
+                "<#{}> {} - **{}** needed, {}, self-star {}, {} {}",
+                starboard.channel_id,
+                starboard_emojis,
+                starboard.threshold,
+                if starboard.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                },
+                if starboard.allow_selfstar {
+                    "allowed"
+                } else {
+                    "not allowed"
+                },
+                starboard.entry_count,
+                if starboard.entry_count == 1 {
+                    "entry"
+                } else {
+                    "entries"
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::default()
+                .title("Starboards")
+                .description(lines.join("\n"))
+                .colour(Colour::GOLD),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
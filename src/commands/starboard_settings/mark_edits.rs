@@ -0,0 +1,36 @@
+use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
+use poise::serenity_prelude::Channel;
+use sqlx::query;
+
+/// Change whether starboard messages show when the original message was edited.
+#[poise::command(rename = "mark-edits", prefix_command, slash_command, guild_only)]
+pub async fn mark_edits_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "Show an '(edited)' marker on edited messages"] mark_edits: bool,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    query!(
+        "UPDATE starboards SET mark_edits = ?1 WHERE channel_id = ?2",
+        mark_edits,
+        channel_id,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+
+    ctx.say(format!(
+        "Updated starboard setting 'mark edits' to **{}**.",
+        mark_edits
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::{
+    Error, PoiseContext,
+    commands::{format_duration, parse_duration, starboard_setup_in_channel},
+};
+use poise::serenity_prelude::{Channel, Mentionable};
+use sqlx::query;
+
+/// Change the maximum age of messages that can be newly posted to a starboard.
+#[poise::command(rename = "max-message-age", prefix_command, slash_command, guild_only)]
+pub async fn max_message_age_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "The maximum message age (e.g. 7d, 12h) or 0 to disable"] age: String,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let Some(age) = parse_duration(&age) else {
+        ctx.say("Invalid duration. Use a duration such as `30m`, `12h`, `7d` or `0` to disable.")
+            .await?;
+        return Ok(());
+    };
+
+    query!(
+        "UPDATE starboards SET max_message_age = ?1 WHERE channel_id = ?2",
+        age,
+        channel_id,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+
+    match age {
+        0 => {
+            ctx.say(format!(
+                "Messages of any age can now be posted to the starboard in {}.",
+                starboard.mention()
+            ))
+            .await?;
+        }
+        age => {
+            ctx.say(format!(
+                "Messages older than **{}** will no longer be newly posted to the starboard in {}. Existing entries will still be updated.",
+                format_duration(age),
+                starboard.mention()
+            ))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
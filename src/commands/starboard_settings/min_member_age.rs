@@ -0,0 +1,54 @@
+use crate::{
+    Error, PoiseContext,
+    commands::{format_duration, parse_duration, starboard_setup_in_channel},
+};
+use poise::serenity_prelude::{Channel, Mentionable};
+use sqlx::query;
+
+/// Change the minimum time in the server for reactions to count towards a starboard.
+#[poise::command(rename = "min-member-age", prefix_command, slash_command, guild_only)]
+pub async fn min_member_age_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "The minimum time in the server (e.g. 7d, 12h) or 0 to disable"] age: String,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let Some(age) = parse_duration(&age) else {
+        ctx.say("Invalid duration. Use a duration such as `30m`, `12h`, `7d` or `0` to disable.")
+            .await?;
+        return Ok(());
+    };
+
+    query!(
+        "UPDATE starboards SET min_member_age = ?1 WHERE channel_id = ?2",
+        age,
+        channel_id,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+
+    match age {
+        0 => {
+            ctx.say(format!("Reactions from members that joined at any time now count towards the starboard in {}.", starboard.mention()))
+                .await?;
+        }
+        age => {
+            ctx.say(format!(
+                "Reactions from members that joined less than **{}** ago will no longer count towards the starboard in {}.",
+                format_duration(age),
+                starboard.mention()
+            ))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
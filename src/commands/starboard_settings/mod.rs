@@ -1,13 +1,36 @@
 mod allow_selfstar;
+mod channels;
+mod count_starboard_reactions;
 mod create;
 mod delete;
 mod emoji;
 mod enable;
+mod list;
+mod mark_edits;
+mod max_message_age;
+mod min_account_age;
+mod min_member_age;
+mod nsfw;
+mod original_delete;
+mod reevaluate;
+mod restore;
+mod roles;
+mod show;
 mod threshold;
+mod tier;
+mod unblock;
+mod veto_emoji;
+mod visibility_check;
 
 use self::{
-    allow_selfstar::allow_selfstar, create::create_cmd, delete::delete_cmd, emoji::emoji_cmd,
-    enable::enable_cmd, threshold::threshold_cmd,
+    allow_selfstar::allow_selfstar, channels::channels_cmd,
+    count_starboard_reactions::count_starboard_reactions_cmd, create::create_cmd,
+    delete::delete_cmd, emoji::emoji_cmd, enable::enable_cmd, list::list_cmd,
+    mark_edits::mark_edits_cmd, max_message_age::max_message_age_cmd,
+    min_account_age::min_account_age_cmd, min_member_age::min_member_age_cmd, nsfw::nsfw_cmd,
+    original_delete::original_delete_cmd, restore::restore_cmd, roles::roles_cmd, show::show_cmd,
+    threshold::threshold_cmd, tier::tier_cmd, unblock::unblock_cmd, veto_emoji::veto_emoji_cmd,
+    visibility_check::visibility_check_cmd,
 };
 use crate::PoiseContext;
 use anyhow::Result;
@@ -23,12 +46,28 @@ use anyhow::Result;
     guild_cooldown = 5s,
     subcommand_required,
     subcommands(
+        "list_cmd",
+        "show_cmd",
         "create_cmd",
         "delete_cmd",
+        "restore_cmd",
         "enable_cmd",
         "threshold_cmd",
         "emoji_cmd",
-        "allow_selfstar"
+        "allow_selfstar",
+        "mark_edits_cmd",
+        "original_delete_cmd",
+        "unblock_cmd",
+        "count_starboard_reactions_cmd",
+        "channels_cmd",
+        "visibility_check_cmd",
+        "nsfw_cmd",
+        "roles_cmd",
+        "min_account_age_cmd",
+        "min_member_age_cmd",
+        "veto_emoji_cmd",
+        "max_message_age_cmd",
+        "tier_cmd"
     )
 )]
 pub async fn starboard_settings_sub(_: PoiseContext<'_>) -> Result<()> {
@@ -2,14 +2,14 @@ use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
 use poise::serenity_prelude::Channel;
 use sqlx::query;
 
-/// Change the 'star' emoji for a starboard.
-#[poise::command(rename = "emoji", prefix_command, slash_command, guild_only)]
-pub async fn emoji_cmd(
+/// Change whether a starboard accepts messages from age-restricted channels.
+#[poise::command(rename = "nsfw", prefix_command, slash_command, guild_only)]
+pub async fn nsfw_cmd(
     ctx: PoiseContext<'_>,
     #[channel_types("Text")]
     #[description = "The starboard to configure"]
     starboard: Channel,
-    #[description = "The new emoji to use as the 'star'"] emoji: String,
+    #[description = "Accept messages from age-restricted channels"] nsfw: bool,
 ) -> Result<(), Error> {
     let channel_id = starboard.id().get().try_into()?;
     if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
@@ -18,20 +18,15 @@ pub async fn emoji_cmd(
         return Ok(());
     }
 
-    if emojis::get(&emoji).is_none() {
-        ctx.say("Invalid or unknown emoji. You can only use Discord's default emojis for the starboard.")
-            .await?;
-        return Ok(());
-    };
-
     query!(
-        "UPDATE starboards SET emoji = ?1 WHERE channel_id = ?2",
-        emoji,
+        "UPDATE starboards SET nsfw = ?1 WHERE channel_id = ?2",
+        nsfw,
         channel_id,
     )
     .execute(ctx.data().database.pool())
     .await?;
-    ctx.say(format!("Updated starboard emoji to {}.", emoji))
+
+    ctx.say(format!("Updated starboard setting 'nsfw' to **{}**.", nsfw))
         .await?;
 
     Ok(())
@@ -0,0 +1,44 @@
+use crate::{
+    Error, PoiseContext, commands::starboard_setup_in_channel, delete_policy::DeletePolicy,
+};
+use poise::{ChoiceParameter, serenity_prelude::Channel};
+use sqlx::query;
+
+/// Change what happens to starboard messages when the original message is deleted.
+#[poise::command(
+    rename = "on-original-delete",
+    prefix_command,
+    slash_command,
+    guild_only
+)]
+pub async fn original_delete_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "What to do with the starboard message"] policy: DeletePolicy,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let stored_policy = policy.to_stored();
+    query!(
+        "UPDATE starboards SET original_delete_policy = ?1 WHERE channel_id = ?2",
+        stored_policy,
+        channel_id,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+
+    ctx.say(format!(
+        "Updated starboard setting 'on original delete' to **{}**.",
+        policy.name()
+    ))
+    .await?;
+
+    Ok(())
+}
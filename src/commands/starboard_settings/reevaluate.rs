@@ -0,0 +1,197 @@
+use crate::{
+    PoiseContext,
+    events::{Verdict, reconcile_entry},
+};
+use anyhow::Result;
+use poise::{
+    CreateReply, ReplyHandle,
+    serenity_prelude::{Channel, ChannelId, GetMessages, Message, MessageId},
+};
+use sqlx::query;
+use std::collections::HashSet;
+use tracing::warn;
+
+/// How many recent messages are checked when re-evaluating a channel's history.
+const HISTORY_LIMIT: usize = 200;
+
+/// How many messages are re-evaluated between progress updates.
+const PROGRESS_INTERVAL: usize = 10;
+
+/// Tallies of what happened to the messages that were re-evaluated.
+#[derive(Default)]
+struct Tally {
+    kept: usize,
+    removed: usize,
+    added: usize,
+    skipped: usize,
+    failed: usize,
+}
+
+/// Check that a channel's history can be searched for a starboard, which excludes the starboard itself
+/// and channels from other guilds.
+pub fn recheck_channel_allowed(
+    ctx: PoiseContext<'_>,
+    starboard_channel_id: i64,
+    channel: &Channel,
+) -> bool {
+    match channel {
+        Channel::Guild(channel) => {
+            i64::try_from(channel.id.get()).is_ok_and(|id| id != starboard_channel_id)
+                && Some(channel.guild_id) == ctx.guild_id()
+        }
+        _ => false,
+    }
+}
+
+/// Re-evaluate the existing entries of a starboard after its settings changed, and optionally recent
+/// messages in a channel that may now qualify, reporting progress as it goes.
+pub async fn reevaluate_starboard(
+    ctx: PoiseContext<'_>,
+    starboard_channel_id: i64,
+    history_channel: Option<ChannelId>,
+) -> Result<()> {
+    let entries = query!(
+        "SELECT original_message_id, original_message_channel_id FROM starred_messages
+        WHERE starboard_channel_id = ?1 AND frozen = FALSE",
+        starboard_channel_id
+    )
+    .fetch_all(ctx.data().database.pool())
+    .await?;
+    let mut seen = HashSet::with_capacity(entries.len());
+    let mut tally = Tally::default();
+    let reply = ctx
+        .say(format!(
+            "Re-evaluating {} entries on the starboard in <#{}>...",
+            entries.len(),
+            starboard_channel_id
+        ))
+        .await?;
+
+    for (i, entry) in entries.iter().enumerate() {
+        seen.insert(entry.original_message_id);
+        let message = match ChannelId::new(entry.original_message_channel_id.try_into()?)
+            .message(ctx, MessageId::new(entry.original_message_id.try_into()?))
+            .await
+        {
+            Ok(message) => message,
+            Err(err) => {
+                warn!(
+                    original_message_id = entry.original_message_id,
+                    "Failed to get original message for re-evaluation: {err:?}"
+                );
+                tally.failed += 1;
+                continue;
+            }
+        };
+        match reevaluate_message(ctx, starboard_channel_id, &message).await {
+            Some(Verdict::Present { .. }) => tally.kept += 1,
+            Some(Verdict::Absent { .. }) => tally.removed += 1,
+            Some(Verdict::Skipped(_)) => tally.skipped += 1,
+            None => tally.failed += 1,
+        }
+        if (i + 1) % PROGRESS_INTERVAL == 0 {
+            report_progress(
+                ctx,
+                &reply,
+                format!(
+                    "Re-evaluating entries on the starboard in <#{}>... {}/{}",
+                    starboard_channel_id,
+                    i + 1,
+                    entries.len()
+                ),
+            )
+            .await;
+        }
+    }
+
+    // Recent messages that were never posted may qualify now too.
+    if let Some(history_channel) = history_channel {
+        report_progress(
+            ctx,
+            &reply,
+            format!(
+                "Checking recent messages in <#{}> for the starboard in <#{}>...",
+                history_channel, starboard_channel_id
+            ),
+        )
+        .await;
+        let mut before = None;
+        let mut checked = 0;
+        while checked < HISTORY_LIMIT {
+            let mut request = GetMessages::new().limit(100);
+            if let Some(before) = before {
+                request = request.before(before);
+            }
+            let messages = history_channel.messages(ctx, request).await?;
+            let Some(last) = messages.last() else {
+                break;
+            };
+            before = Some(last.id);
+            checked += messages.len();
+            for message in &messages {
+                if message.reactions.is_empty() || seen.contains(&i64::try_from(message.id.get())?)
+                {
+                    continue;
+                }
+                match reevaluate_message(ctx, starboard_channel_id, message).await {
+                    Some(Verdict::Present { .. }) => tally.added += 1,
+                    Some(_) => {}
+                    None => tally.failed += 1,
+                }
+            }
+        }
+    }
+
+    let summary = format!(
+        "Re-evaluated the starboard in <#{}>: **{}** kept, **{}** removed, **{}** added, **{}** left as is, **{}** failed.",
+        starboard_channel_id, tally.kept, tally.removed, tally.added, tally.skipped, tally.failed
+    );
+    // Long passes can outlive the interaction, so the summary is sent to the channel instead.
+    if !report_progress(ctx, &reply, summary.clone()).await {
+        ctx.channel_id().say(ctx, summary).await?;
+    }
+
+    Ok(())
+}
+
+/// Edit the progress message, logging rather than failing the pass when it can't be edited.
+///
+/// Interaction responses can only be edited for 15 minutes, which a long pass may exceed.
+async fn report_progress(ctx: PoiseContext<'_>, reply: &ReplyHandle<'_>, content: String) -> bool {
+    match reply
+        .edit(ctx, CreateReply::default().content(content))
+        .await
+    {
+        Ok(()) => true,
+        Err(err) => {
+            warn!("Failed to update re-evaluation progress: {err:?}");
+            false
+        }
+    }
+}
+
+/// Reconcile a single message, logging rather than failing the whole pass on errors.
+async fn reevaluate_message(
+    ctx: PoiseContext<'_>,
+    starboard_channel_id: i64,
+    message: &Message,
+) -> Option<Verdict> {
+    match reconcile_entry(
+        ctx.serenity_context(),
+        ctx.data(),
+        starboard_channel_id,
+        message,
+        false,
+    )
+    .await
+    {
+        Ok(verdict) => Some(verdict),
+        Err(err) => {
+            warn!(
+                message_id = message.id.get(),
+                "Failed to re-evaluate starboard entry: {err:?}"
+            );
+            None
+        }
+    }
+}
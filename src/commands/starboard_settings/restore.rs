@@ -0,0 +1,53 @@
+use crate::{Error, PoiseContext, starboard_purge::RESTORE_WINDOW};
+use poise::serenity_prelude::{Channel, Mentionable, Timestamp};
+use sqlx::query;
+
+/// Restore a recently deleted starboard along with its entries.
+#[poise::command(rename = "restore", prefix_command, slash_command, guild_only)]
+pub async fn restore_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The channel of the deleted starboard"]
+    starboard: Channel,
+) -> Result<(), Error> {
+    let guild_id: i64 = match ctx.guild_id() {
+        Some(g) => g.get().try_into()?,
+        None => {
+            ctx.say("This command can only be used in a guild.").await?;
+            return Ok(());
+        }
+    };
+    let channel_id: i64 = starboard.id().get().try_into()?;
+    let Some(deleted) = query!(
+        r#"SELECT deleted_at AS "deleted_at!: i64" FROM starboards
+        WHERE channel_id = ?1 AND guild_id = ?2 AND deleted_at IS NOT NULL"#,
+        channel_id,
+        guild_id
+    )
+    .fetch_optional(ctx.data().database.pool())
+    .await?
+    else {
+        ctx.say("There is no deleted starboard for that channel.")
+            .await?;
+        return Ok(());
+    };
+    if Timestamp::now().unix_timestamp() - deleted.deleted_at > RESTORE_WINDOW {
+        ctx.say("The starboard for that channel was deleted too long ago to be restored.")
+            .await?;
+        return Ok(());
+    }
+
+    query!(
+        "UPDATE starboards SET deleted_at = NULL WHERE channel_id = ?1",
+        channel_id
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+    ctx.say(format!(
+        "The starboard in {} has been restored along with its entries.",
+        starboard.mention()
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use super::remove_unused_role_rules;
+use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
+use poise::serenity_prelude::{Channel, Mentionable, Role};
+use sqlx::query;
+
+/// Change whether reactors with a role are ignored by a starboard.
+#[poise::command(rename = "exclude", prefix_command, slash_command, guild_only)]
+pub async fn exclude_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "The role to configure"] role: Role,
+    #[description = "Ignore reactors with this role"] excluded: bool,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let role_id: i64 = role.id.get().try_into()?;
+    query!(
+        "INSERT INTO starboard_role_rules (starboard_channel_id, role_id, excluded) VALUES (?1, ?2, ?3)
+        ON CONFLICT (starboard_channel_id, role_id) DO UPDATE SET excluded = ?3",
+        channel_id,
+        role_id,
+        excluded,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+    remove_unused_role_rules(channel_id, ctx.data().database.pool()).await?;
+
+    match excluded {
+        true => {
+            ctx.say(format!(
+                "Reactors with {} will no longer count towards the starboard in {}.",
+                role.mention(),
+                starboard.mention()
+            ))
+            .await?;
+        }
+        false => {
+            ctx.say(format!(
+                "Reactors with {} can count towards the starboard in {} again.",
+                role.mention(),
+                starboard.mention()
+            ))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
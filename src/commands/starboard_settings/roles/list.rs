@@ -0,0 +1,63 @@
+use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
+use poise::serenity_prelude::{Channel, Mentionable};
+use sqlx::query;
+
+/// List the role rules for a starboard.
+#[poise::command(rename = "list", prefix_command, slash_command, guild_only)]
+pub async fn list_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to view"]
+    starboard: Channel,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let role_rules = query!(
+        "SELECT role_id, required, excluded, weight FROM starboard_role_rules WHERE starboard_channel_id = ?1",
+        channel_id
+    )
+    .fetch_all(ctx.data().database.pool())
+    .await?;
+    if role_rules.is_empty() {
+        ctx.say(format!(
+            "Every reactor counts once towards the starboard in {}.",
+            starboard.mention()
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let format_roles = |roles: Vec<String>| match roles.is_empty() {
+        true => "*None*".to_string(),
+        false => roles.join(" "),
+    };
+    let required = role_rules
+        .iter()
+        .filter(|r| r.required)
+        .map(|r| format!("<@&{}>", r.role_id))
+        .collect();
+    let excluded = role_rules
+        .iter()
+        .filter(|r| r.excluded)
+        .map(|r| format!("<@&{}>", r.role_id))
+        .collect();
+    let weights = role_rules
+        .iter()
+        .filter_map(|r| r.weight.map(|w| format!("<@&{}> ({})", r.role_id, w)))
+        .collect();
+    ctx.say(format!(
+        "Role rules for the starboard in {}:\n**Required:** {}\n**Excluded:** {}\n**Weights:** {}",
+        starboard.mention(),
+        format_roles(required),
+        format_roles(excluded),
+        format_roles(weights)
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+mod exclude;
+mod list;
+mod require;
+mod weight;
+
+use self::{exclude::exclude_cmd, list::list_cmd, require::require_cmd, weight::weight_cmd};
+use crate::{PoiseContext, database::DatabasePool};
+use anyhow::Result;
+use sqlx::query;
+
+/// Manage which roles can count towards a starboard and how much they count.
+#[poise::command(
+    rename = "roles",
+    prefix_command,
+    slash_command,
+    guild_only,
+    subcommand_required,
+    subcommands("require_cmd", "exclude_cmd", "weight_cmd", "list_cmd")
+)]
+pub async fn roles_cmd(_: PoiseContext<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Remove role rules that no longer change how reactors are counted.
+async fn remove_unused_role_rules(starboard_channel_id: i64, pool: &DatabasePool) -> Result<()> {
+    query!(
+        "DELETE FROM starboard_role_rules
+        WHERE starboard_channel_id = ?1 AND required = FALSE AND excluded = FALSE AND weight IS NULL",
+        starboard_channel_id
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use super::remove_unused_role_rules;
+use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
+use poise::serenity_prelude::{Channel, Mentionable, Role};
+use sqlx::query;
+
+/// Change whether a role is one of the roles reactors need to count towards a starboard.
+#[poise::command(rename = "require", prefix_command, slash_command, guild_only)]
+pub async fn require_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "The role to configure"] role: Role,
+    #[description = "Only count reactors with this or another required role"] required: bool,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let role_id: i64 = role.id.get().try_into()?;
+    query!(
+        "INSERT INTO starboard_role_rules (starboard_channel_id, role_id, required) VALUES (?1, ?2, ?3)
+        ON CONFLICT (starboard_channel_id, role_id) DO UPDATE SET required = ?3",
+        channel_id,
+        role_id,
+        required,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+    remove_unused_role_rules(channel_id, ctx.data().database.pool()).await?;
+
+    match required {
+        true => {
+            ctx.say(format!(
+                "Reactors now need {} or another required role to count towards the starboard in {}.",
+                role.mention(),
+                starboard.mention()
+            ))
+            .await?;
+        }
+        false => {
+            ctx.say(format!(
+                "{} is no longer a required role for the starboard in {}.",
+                role.mention(),
+                starboard.mention()
+            ))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use super::remove_unused_role_rules;
+use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
+use poise::serenity_prelude::{Channel, Mentionable, Role};
+use sqlx::query;
+
+/// Change how much a reaction from a member with a role counts towards a starboard.
+#[poise::command(rename = "weight", prefix_command, slash_command, guild_only)]
+pub async fn weight_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "The role to configure"] role: Role,
+    #[description = "How much each reaction counts, leave empty to reset to 1"]
+    #[max = 100]
+    weight: Option<u32>,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let role_id: i64 = role.id.get().try_into()?;
+    query!(
+        "INSERT INTO starboard_role_rules (starboard_channel_id, role_id, weight) VALUES (?1, ?2, ?3)
+        ON CONFLICT (starboard_channel_id, role_id) DO UPDATE SET weight = ?3",
+        channel_id,
+        role_id,
+        weight,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+    remove_unused_role_rules(channel_id, ctx.data().database.pool()).await?;
+
+    ctx.say(format!(
+        "Reactions from members with {} now count as **{}** towards the starboard in {}.",
+        role.mention(),
+        weight.unwrap_or(1),
+        starboard.mention()
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use crate::{
+    Error, PoiseContext, commands::format_duration, delete_policy::DeletePolicy,
+    emoji::StarboardEmoji,
+};
+use poise::{
+    ChoiceParameter, CreateReply,
+    serenity_prelude::{Channel, Colour, CreateEmbed, Mentionable},
+};
+use sqlx::query;
+
+/// Show every setting of a starboard.
+#[poise::command(rename = "show", prefix_command, slash_command, guild_only)]
+pub async fn show_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to view"]
+    starboard: Channel,
+) -> Result<(), Error> {
+    let channel_id: i64 = starboard.id().get().try_into()?;
+    let pool = ctx.data().database.pool();
+    let Some(settings) = query!(
+        r#"SELECT enabled, threshold, allow_selfstar, mark_edits, original_delete_policy,
+            count_starboard_reactions, visibility_check, visibility_role_id, nsfw, min_account_age,
+            min_member_age, veto_emoji, max_message_age, parent_starboard_id, require_parent,
+            remove_on_promotion,
+            (SELECT COUNT(*) FROM starred_messages WHERE starboard_channel_id = ?1) AS "entry_count!: i64",
+            (SELECT COUNT(*) FROM blocked_messages WHERE starboard_channel_id = ?1) AS "blocked_count!: i64",
+            (SELECT COUNT(*) FROM starboard_channel_filters WHERE starboard_channel_id = ?1 AND include = TRUE) AS "included_count!: i64",
+            (SELECT COUNT(*) FROM starboard_channel_filters WHERE starboard_channel_id = ?1 AND include = FALSE) AS "excluded_count!: i64",
+            (SELECT COUNT(*) FROM starboard_role_rules WHERE starboard_channel_id = ?1) AS "role_rule_count!: i64"
+        FROM starboards WHERE channel_id = ?1 AND deleted_at IS NULL"#,
+        channel_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    };
+    let emojis = query!(
+        "SELECT emoji FROM starboard_emojis WHERE starboard_channel_id = ?1 ORDER BY rowid",
+        channel_id
+    )
+    .fetch_all(pool)
+    .await?
+    .iter()
+    .map(|row| StarboardEmoji::from_stored(&row.emoji).to_string())
+    .collect::<Vec<_>>();
+
+    let yes_no = |value: bool| if value { "Yes" } else { "No" }.to_string();
+    let duration_or = |seconds: i64, disabled: &str| match seconds {
+        0 => disabled.to_string(),
+        seconds => format_duration(seconds),
+    };
+    let tier = match settings.parent_starboard_id {
+        Some(parent_starboard_id) => format!(
+            "Above <#{}>{}",
+            parent_starboard_id,
+            if settings.require_parent {
+                ", entries must be on it first"
+            } else {
+                ""
+            }
+        ),
+        None => "None".to_string(),
+    };
+    let visibility = match (settings.visibility_check, settings.visibility_role_id) {
+        (false, _) => "Disabled".to_string(),
+        (true, Some(role_id)) => format!("Enabled for <@&{}>", role_id),
+        (true, None) => "Enabled for @everyone".to_string(),
+    };
+    let fields = [
+        ("Enabled", yes_no(settings.enabled)),
+        ("Threshold", settings.threshold.to_string()),
+        ("Emojis", emojis.join(" ")),
+        (
+            "Veto emoji",
+            settings
+                .veto_emoji
+                .as_deref()
+                .map(|e| StarboardEmoji::from_stored(e).to_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ),
+        ("Self-star", yes_no(settings.allow_selfstar)),
+        ("Mark edits", yes_no(settings.mark_edits)),
+        (
+            "On original delete",
+            DeletePolicy::from_stored(&settings.original_delete_policy)
+                .name()
+                .to_string(),
+        ),
+        (
+            "Count starboard reactions",
+            yes_no(settings.count_starboard_reactions),
+        ),
+        ("Visibility check", visibility),
+        ("NSFW", yes_no(settings.nsfw)),
+        (
+            "Min account age",
+            duration_or(settings.min_account_age, "None"),
+        ),
+        (
+            "Min member age",
+            duration_or(settings.min_member_age, "None"),
+        ),
+        (
+            "Max message age",
+            duration_or(settings.max_message_age, "None"),
+        ),
+        ("Lower tier", tier),
+        (
+            "Remove promoted entries",
+            yes_no(settings.remove_on_promotion),
+        ),
+        (
+            "Channel rules",
+            format!(
+                "{} included, {} excluded",
+                settings.included_count, settings.excluded_count
+            ),
+        ),
+        ("Role rules", settings.role_rule_count.to_string()),
+        (
+            "Entries",
+            format!(
+                "{} posted, {} blocked",
+                settings.entry_count, settings.blocked_count
+            ),
+        ),
+    ];
+
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::default()
+                .title("Starboard settings")
+                .description(format!(
+                    "Settings for the starboard in {}.",
+                    starboard.mention()
+                ))
+                .fields(fields.into_iter().map(|(name, value)| (name, value, true)))
+                .colour(Colour::GOLD),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use super::reevaluate::{recheck_channel_allowed, reevaluate_starboard};
 use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
 use poise::serenity_prelude::Channel;
 use sqlx::query;
@@ -10,13 +11,24 @@ pub async fn threshold_cmd(
     #[description = "The starboard to configure"]
     starboard: Channel,
     #[description = "The amount of reactions needed to post to the starboard"] threshold: u32,
+    #[description = "Re-evaluate existing entries with the new setting"] reevaluate: Option<bool>,
+    #[channel_types("Text")]
+    #[description = "Also check recent messages in this channel"]
+    recheck_channel: Option<Channel>,
 ) -> Result<(), Error> {
-    let channel_id = starboard.id().get().try_into()?;
+    let channel_id: i64 = starboard.id().get().try_into()?;
     if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
         ctx.say("A starboard does not exist for that channel.")
             .await?;
         return Ok(());
     }
+    if let Some(recheck_channel) = &recheck_channel
+        && !recheck_channel_allowed(ctx, channel_id, recheck_channel)
+    {
+        ctx.say("Recent messages can only be checked in another channel of this server.")
+            .await?;
+        return Ok(());
+    }
 
     query!(
         "UPDATE starboards SET threshold = ?1 WHERE channel_id = ?2",
@@ -32,5 +44,9 @@ pub async fn threshold_cmd(
     ))
     .await?;
 
+    if reevaluate.unwrap_or(false) || recheck_channel.is_some() {
+        reevaluate_starboard(ctx, channel_id, recheck_channel.map(|c| c.id())).await?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,150 @@
+use crate::{Error, PoiseContext, commands::starboard_in_guild};
+use anyhow::Result;
+use poise::serenity_prelude::{Channel, Mentionable};
+use sqlx::query;
+
+/// Place a starboard above a lower tier starboard that messages are promoted from.
+#[poise::command(rename = "tier", prefix_command, slash_command, guild_only)]
+pub async fn tier_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[channel_types("Text")]
+    #[description = "The lower tier starboard, leave empty to make this starboard standalone"]
+    lower_tier: Option<Channel>,
+    #[description = "Whether new entries must already be on the lower tier"]
+    require_lower_tier: Option<bool>,
+    #[description = "Whether promoted messages are removed from the lower tier"]
+    remove_from_lower_tier: Option<bool>,
+) -> Result<(), Error> {
+    let pool = ctx.data().database.pool();
+    let channel_id: i64 = starboard.id().get().try_into()?;
+    if !starboard_in_guild(ctx, channel_id).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let previous_parent_id = query!(
+        "SELECT parent_starboard_id FROM starboards WHERE channel_id = ?1",
+        channel_id
+    )
+    .fetch_one(pool)
+    .await?
+    .parent_starboard_id;
+
+    let Some(lower_tier) = lower_tier else {
+        query!(
+            "UPDATE starboards SET parent_starboard_id = NULL, require_parent = FALSE WHERE channel_id = ?1",
+            channel_id,
+        )
+        .execute(pool)
+        .await?;
+        if let Some(previous_parent_id) = previous_parent_id {
+            reset_remove_on_promotion(ctx, previous_parent_id).await?;
+        }
+        ctx.say(format!(
+            "The starboard in {} is no longer a higher tier of another starboard.",
+            starboard.mention()
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let parent_id: i64 = lower_tier.id().get().try_into()?;
+    if parent_id == channel_id {
+        ctx.say("A starboard can't be its own lower tier.").await?;
+        return Ok(());
+    }
+    if !starboard_in_guild(ctx, parent_id).await? {
+        ctx.say("A starboard does not exist for the lower tier channel.")
+            .await?;
+        return Ok(());
+    }
+
+    // Walk down from the lower tier to make sure this wouldn't create a loop.
+    let mut current = Some(parent_id);
+    while let Some(id) = current {
+        if id == channel_id {
+            ctx.say(format!(
+                "{} is already a lower tier of {}.",
+                starboard.mention(),
+                lower_tier.mention()
+            ))
+            .await?;
+            return Ok(());
+        }
+        current = query!(
+            "SELECT parent_starboard_id FROM starboards WHERE channel_id = ?1",
+            id
+        )
+        .fetch_optional(pool)
+        .await?
+        .and_then(|row| row.parent_starboard_id);
+    }
+
+    let require_lower_tier = require_lower_tier.unwrap_or(false);
+    query!(
+        "UPDATE starboards SET parent_starboard_id = ?1, require_parent = ?2 WHERE channel_id = ?3",
+        parent_id,
+        require_lower_tier,
+        channel_id,
+    )
+    .execute(pool)
+    .await?;
+    if let Some(previous_parent_id) = previous_parent_id
+        && previous_parent_id != parent_id
+    {
+        reset_remove_on_promotion(ctx, previous_parent_id).await?;
+    }
+
+    // The setting belongs to the lower tier, so it's only changed when given.
+    if let Some(remove_from_lower_tier) = remove_from_lower_tier {
+        query!(
+            "UPDATE starboards SET remove_on_promotion = ?1 WHERE channel_id = ?2",
+            remove_from_lower_tier,
+            parent_id,
+        )
+        .execute(pool)
+        .await?;
+    }
+    let remove_from_lower_tier = query!(
+        "SELECT remove_on_promotion FROM starboards WHERE channel_id = ?1",
+        parent_id
+    )
+    .fetch_one(pool)
+    .await?
+    .remove_on_promotion;
+
+    ctx.say(format!(
+        "Messages will now be promoted from {} to {}.{}{}",
+        lower_tier.mention(),
+        starboard.mention(),
+        if require_lower_tier {
+            " New entries must already be on the lower tier."
+        } else {
+            ""
+        },
+        if remove_from_lower_tier {
+            " Promoted messages will be removed from the lower tier."
+        } else {
+            ""
+        },
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Stop a starboard removing promoted entries once no higher tier starboards promote from it.
+async fn reset_remove_on_promotion(ctx: PoiseContext<'_>, starboard_channel_id: i64) -> Result<()> {
+    query!(
+        "UPDATE starboards SET remove_on_promotion = FALSE
+        WHERE channel_id = ?1 AND NOT EXISTS (SELECT 1 FROM starboards WHERE parent_starboard_id = ?1)",
+        starboard_channel_id
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::{
+    Error, PoiseContext,
+    commands::{parse_message_id, starboard_setup_in_channel},
+};
+use poise::serenity_prelude::{Channel, Mentionable};
+use sqlx::query;
+
+/// Allow a message that was removed from a starboard by a moderator to be posted again.
+#[poise::command(rename = "unblock", prefix_command, slash_command, guild_only)]
+pub async fn unblock_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "A link to or the ID of the original message"] message: String,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let Some(message_id) = parse_message_id(&message) else {
+        ctx.say("Invalid message. Provide a message link or ID.")
+            .await?;
+        return Ok(());
+    };
+    let message_id: i64 = message_id.get().try_into()?;
+
+    let result = query!(
+        "DELETE FROM blocked_messages WHERE starboard_channel_id = ?1 AND original_message_id = ?2",
+        channel_id,
+        message_id,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+    if result.rows_affected() == 0 {
+        ctx.say(format!(
+            "That message is not blocked from the starboard in {}.",
+            starboard.mention()
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    ctx.say(format!(
+        "That message can now be posted to the starboard in {} again.",
+        starboard.mention()
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::{
+    Error, PoiseContext,
+    commands::{parse_starboard_emoji, starboard_setup_in_channel},
+};
+use poise::serenity_prelude::{Channel, Mentionable};
+use sqlx::query;
+
+/// Change the veto emoji whose reactions are subtracted from a starboard's count.
+#[poise::command(rename = "veto-emoji", prefix_command, slash_command, guild_only)]
+pub async fn veto_emoji_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "The emoji to use as a veto, leave empty to disable"] emoji: Option<String>,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let Some(emoji) = emoji else {
+        query!(
+            "UPDATE starboards SET veto_emoji = NULL WHERE channel_id = ?1",
+            channel_id,
+        )
+        .execute(ctx.data().database.pool())
+        .await?;
+        ctx.say(format!(
+            "Removed the veto emoji from the starboard in {}.",
+            starboard.mention()
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let Some(emoji) = parse_starboard_emoji(ctx, &emoji).await? else {
+        ctx.say("Invalid or unknown emoji. You can only use Discord's default emojis or this server's custom emojis for the starboard.")
+            .await?;
+        return Ok(());
+    };
+    let stored_emoji = emoji.to_stored();
+    if query!(
+        "SELECT emoji FROM starboard_emojis WHERE starboard_channel_id = ?1 AND emoji = ?2",
+        channel_id,
+        stored_emoji,
+    )
+    .fetch_optional(ctx.data().database.pool())
+    .await?
+    .is_some()
+    {
+        ctx.say(format!(
+            "{} is already a 'star' for that starboard and can't also be the veto emoji.",
+            emoji
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    query!(
+        "UPDATE starboards SET veto_emoji = ?1 WHERE channel_id = ?2",
+        stored_emoji,
+        channel_id,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+    ctx.say(format!(
+        "Reactions with {} will now be subtracted from the count on the starboard in {}.",
+        emoji,
+        starboard.mention()
+    ))
+    .await?;
+
+    Ok(())
+}
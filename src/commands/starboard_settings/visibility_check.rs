@@ -0,0 +1,60 @@
+use crate::{Error, PoiseContext, commands::starboard_setup_in_channel};
+use poise::serenity_prelude::{Channel, Mentionable, Role};
+use sqlx::query;
+
+/// Change whether messages from channels more private than the starboard can be posted.
+#[poise::command(rename = "visibility-check", prefix_command, slash_command, guild_only)]
+pub async fn visibility_check_cmd(
+    ctx: PoiseContext<'_>,
+    #[channel_types("Text")]
+    #[description = "The starboard to configure"]
+    starboard: Channel,
+    #[description = "Refuse to post messages the role can't see in their original channel"]
+    enabled: bool,
+    #[description = "The role to compare channel visibility for (defaults to @everyone)"]
+    role: Option<Role>,
+) -> Result<(), Error> {
+    let channel_id = starboard.id().get().try_into()?;
+    if !starboard_setup_in_channel(channel_id, ctx.data().database.pool()).await? {
+        ctx.say("A starboard does not exist for that channel.")
+            .await?;
+        return Ok(());
+    }
+
+    let role_id: Option<i64> = role.as_ref().map(|r| r.id.get().try_into()).transpose()?;
+    query!(
+        "UPDATE starboards SET visibility_check = ?1, visibility_role_id = ?2 WHERE channel_id = ?3",
+        enabled,
+        role_id,
+        channel_id,
+    )
+    .execute(ctx.data().database.pool())
+    .await?;
+
+    match (enabled, role) {
+        (true, Some(role)) => {
+            ctx.say(format!(
+                "The starboard in {} will not post messages that {} can't see in their original channel.",
+                starboard.mention(),
+                role.mention()
+            ))
+            .await?;
+        }
+        (true, None) => {
+            ctx.say(format!(
+                "The starboard in {} will not post messages that @everyone can't see in their original channel.",
+                starboard.mention()
+            ))
+            .await?;
+        }
+        (false, _) => {
+            ctx.say(format!(
+                "The starboard in {} will post messages from any channel, including private ones.",
+                starboard.mention()
+            ))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
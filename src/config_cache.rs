@@ -0,0 +1,102 @@
+use crate::{database::DatabasePool, emoji::StarboardEmoji};
+use anyhow::Result;
+use poise::serenity_prelude::GuildId;
+use sqlx::query;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+/// The configuration of a starboard needed to decide whether a reaction is relevant to it.
+#[derive(Debug, Clone)]
+pub struct CachedStarboard {
+    pub channel_id: i64,
+    pub enabled: bool,
+    pub count_starboard_reactions: bool,
+    pub parent_starboard_id: Option<i64>,
+    pub emojis: Vec<StarboardEmoji>,
+    pub veto_emoji: Option<StarboardEmoji>,
+}
+
+impl CachedStarboard {
+    /// Whether reactions with the given emoji affect the starboard.
+    pub fn uses_emoji(&self, emoji: &StarboardEmoji) -> bool {
+        self.emojis.contains(emoji) || self.veto_emoji.as_ref() == Some(emoji)
+    }
+}
+
+/// An in-memory cache of the starboards configured in each guild.
+///
+/// Loaded from the database the first time a guild is needed and invalidated whenever its settings change.
+#[derive(Debug, Default)]
+pub struct ConfigCache {
+    guilds: RwLock<HashMap<GuildId, Arc<[CachedStarboard]>>>,
+    generation: AtomicU64,
+}
+
+impl ConfigCache {
+    /// Get the starboards configured in a guild, loading them from the database if they aren't cached.
+    pub async fn starboards(
+        &self,
+        pool: &DatabasePool,
+        guild_id: GuildId,
+    ) -> Result<Arc<[CachedStarboard]>> {
+        if let Some(starboards) = self
+            .guilds
+            .read()
+            .expect("config cache poisoned")
+            .get(&guild_id)
+        {
+            return Ok(starboards.clone());
+        }
+
+        let generation = self.generation.load(Ordering::Acquire);
+        let guild_id_db: i64 = guild_id.get().try_into()?;
+        let emojis = query!(
+            "SELECT starboard_channel_id, emoji FROM starboard_emojis
+            WHERE starboard_channel_id IN (SELECT channel_id FROM starboards WHERE guild_id = ?1 AND deleted_at IS NULL)
+            ORDER BY rowid",
+            guild_id_db
+        )
+        .fetch_all(pool)
+        .await?;
+        let starboards = query!(
+            "SELECT channel_id, enabled, count_starboard_reactions, parent_starboard_id, veto_emoji
+            FROM starboards WHERE guild_id = ?1 AND deleted_at IS NULL",
+            guild_id_db
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| CachedStarboard {
+            channel_id: row.channel_id,
+            enabled: row.enabled,
+            count_starboard_reactions: row.count_starboard_reactions,
+            parent_starboard_id: row.parent_starboard_id,
+            emojis: emojis
+                .iter()
+                .filter(|e| e.starboard_channel_id == row.channel_id)
+                .map(|e| StarboardEmoji::from_stored(&e.emoji))
+                .collect(),
+            veto_emoji: row.veto_emoji.as_deref().map(StarboardEmoji::from_stored),
+        })
+        .collect::<Arc<[_]>>();
+
+        // Don't cache what was loaded if the settings changed in the meantime.
+        let mut guilds = self.guilds.write().expect("config cache poisoned");
+        if self.generation.load(Ordering::Acquire) == generation {
+            guilds.insert(guild_id, starboards.clone());
+        }
+        Ok(starboards)
+    }
+
+    /// Forget the cached starboards of a guild so they are loaded again on next use.
+    pub fn invalidate(&self, guild_id: GuildId) {
+        let mut guilds = self.guilds.write().expect("config cache poisoned");
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        guilds.remove(&guild_id);
+    }
+}
@@ -0,0 +1,33 @@
+/// What happens to a starboard message when the original message is deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum DeletePolicy {
+    /// Delete the starboard message.
+    #[name = "Delete the starboard message"]
+    Delete,
+    /// Keep the starboard message but mark that the original was deleted.
+    #[name = "Keep the starboard message as an archive"]
+    Archive,
+    /// Keep the starboard message untouched.
+    #[name = "Keep the starboard message untouched"]
+    Keep,
+}
+
+impl DeletePolicy {
+    /// Read a delete policy from the form used in storage, falling back to [`DeletePolicy::Delete`].
+    pub fn from_stored(stored: &str) -> Self {
+        match stored {
+            "archive" => Self::Archive,
+            "keep" => Self::Keep,
+            _ => Self::Delete,
+        }
+    }
+
+    /// Get the form of this delete policy used in storage.
+    pub fn to_stored(self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Archive => "archive",
+            Self::Keep => "keep",
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use poise::serenity_prelude::{EmojiId, ReactionType, parse_emoji};
+use std::fmt;
+
+/// An emoji that can be used as the 'star' of a starboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StarboardEmoji {
+    /// One of Discord's default emojis.
+    Unicode(String),
+    /// A custom emoji belonging to a guild.
+    Custom { id: EmojiId, animated: bool },
+}
+
+impl StarboardEmoji {
+    /// Get the starboard emoji for a reaction.
+    pub fn from_reaction(reaction: &ReactionType) -> Option<Self> {
+        match reaction {
+            ReactionType::Unicode(emoji) => Some(Self::Unicode(emoji.clone())),
+            ReactionType::Custom { animated, id, .. } => Some(Self::Custom {
+                id: *id,
+                animated: *animated,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Parse user input into a starboard emoji.
+    ///
+    /// Accepts either a default emoji or a custom emoji in its `<:name:id>` or `<a:name:id>` form.
+    /// Custom emojis are not checked to exist and must be validated separately.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if let Some(identifier) = parse_emoji(input) {
+            return Some(Self::Custom {
+                id: identifier.id,
+                animated: identifier.animated,
+            });
+        }
+        emojis::get(input).map(|_| Self::Unicode(input.to_string()))
+    }
+
+    /// Read a starboard emoji from the form used in storage.
+    ///
+    /// Custom emojis are stored by id so that renaming them does not break starboards,
+    /// prefixed with `a:` when animated. Default emojis are stored as-is.
+    pub fn from_stored(stored: &str) -> Self {
+        let (animated, id) = match stored.strip_prefix("a:") {
+            Some(id) => (true, id),
+            None => (false, stored),
+        };
+        match id.parse::<u64>() {
+            Ok(id) if id != 0 => Self::Custom {
+                id: EmojiId::new(id),
+                animated,
+            },
+            _ => Self::Unicode(stored.to_string()),
+        }
+    }
+
+    /// Get the form of this emoji used in storage.
+    pub fn to_stored(&self) -> String {
+        match self {
+            Self::Unicode(emoji) => emoji.clone(),
+            Self::Custom { id, animated: true } => format!("a:{id}"),
+            Self::Custom {
+                id,
+                animated: false,
+            } => id.to_string(),
+        }
+    }
+
+    /// Whether the given reaction is this emoji.
+    pub fn matches(&self, reaction: &ReactionType) -> bool {
+        match (self, reaction) {
+            (Self::Unicode(emoji), ReactionType::Unicode(other)) => emoji == other,
+            (Self::Custom { id, .. }, ReactionType::Custom { id: other, .. }) => id == other,
+            _ => false,
+        }
+    }
+
+    /// The CDN image URL of this emoji, if it is a custom emoji.
+    pub fn image_url(&self) -> Option<String> {
+        match self {
+            Self::Unicode(_) => None,
+            Self::Custom { id, animated } => Some(format!(
+                "https://cdn.discordapp.com/emojis/{id}.{}",
+                if *animated { "gif" } else { "png" }
+            )),
+        }
+    }
+}
+
+impl fmt::Display for StarboardEmoji {
+    /// Formats the emoji into a string that will cause Discord clients to render the emoji.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unicode(emoji) => f.write_str(emoji),
+            Self::Custom { id, animated: true } => write!(f, "<a:emoji:{id}>"),
+            Self::Custom {
+                id,
+                animated: false,
+            } => write!(f, "<:emoji:{id}>"),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+/// The lock for a single `(starboard_channel_id, original_message_id)` entry.
+type EntryLock = Arc<AsyncMutex<()>>;
+
+/// Locks that serialize work on a single starboard entry.
+///
+/// Reaction events for the same message can arrive at the same time, and without serializing them each
+/// event could see no existing entry and post its own starboard message.
+#[derive(Debug, Default)]
+pub struct EntryLocks {
+    locks: Mutex<HashMap<(i64, i64), EntryLock>>,
+}
+
+impl EntryLocks {
+    /// Wait for exclusive access to the entry of a message on a starboard, held until the guard is dropped.
+    pub async fn lock(
+        &self,
+        starboard_channel_id: i64,
+        original_message_id: i64,
+    ) -> OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.locks.lock().expect("entry locks poisoned");
+            // Forget locks that nobody is holding or waiting on.
+            locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+            locks
+                .entry((starboard_channel_id, original_message_id))
+                .or_default()
+                .clone()
+        };
+        lock.lock_owned().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EntryLocks;
+
+    #[tokio::test]
+    async fn different_entries_do_not_wait_on_each_other() {
+        let locks = EntryLocks::default();
+        let _first = locks.lock(1, 10).await;
+        let _second = locks.lock(1, 11).await;
+        let _third = locks.lock(2, 10).await;
+    }
+
+    #[tokio::test]
+    async fn released_locks_are_forgotten() {
+        let locks = EntryLocks::default();
+        drop(locks.lock(1, 10).await);
+        drop(locks.lock(1, 11).await);
+        assert_eq!(locks.locks.lock().unwrap().len(), 1);
+    }
+}
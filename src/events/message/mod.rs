@@ -0,0 +1,4 @@
+mod starboard_message_delete;
+mod starboard_message_update;
+pub use starboard_message_delete::starboard_process_message_delete;
+pub use starboard_message_update::starboard_process_message_update;
@@ -0,0 +1,106 @@
+use crate::{AppState, delete_policy::DeletePolicy};
+use ::serenity::all::{ChannelId, EditMessage, MessageId};
+use anyhow::{Error, Result};
+use poise::serenity_prelude as serenity;
+use sqlx::query;
+use tracing::{debug, warn};
+
+pub async fn starboard_process_message_delete(
+    ctx: &serenity::Context,
+    _framework: poise::FrameworkContext<'_, AppState, Error>,
+    data: &AppState,
+    deleted_message_ids: &[MessageId],
+) -> Result<()> {
+    for deleted_message_id in deleted_message_ids {
+        let message_id: i64 = deleted_message_id.get().try_into()?;
+
+        // A starboard message was removed by someone other than the bot, so block the original
+        // from being posted to that starboard again.
+        if let Some(removed_starboard_entry) = query!(
+            "SELECT starboard_channel_id, original_message_id FROM starred_messages WHERE starboard_message_id = ?1",
+            message_id
+        )
+        .fetch_optional(data.database.pool())
+        .await?
+        {
+            query!(
+                "INSERT OR IGNORE INTO blocked_messages (starboard_channel_id, original_message_id) VALUES (?1, ?2)",
+                removed_starboard_entry.starboard_channel_id,
+                removed_starboard_entry.original_message_id
+            )
+            .execute(data.database.pool())
+            .await?;
+            query!(
+                "DELETE FROM starred_messages WHERE starboard_message_id = ?1",
+                message_id
+            )
+            .execute(data.database.pool())
+            .await?;
+            debug!(
+                original_message_id = %removed_starboard_entry.original_message_id,
+                starboard_channel_id = %removed_starboard_entry.starboard_channel_id,
+                "blocked message - starboard message was removed",
+            );
+            continue;
+        }
+
+        let starboard_entries_for_message = query!(
+            "SELECT sm.starboard_channel_id, sm.starboard_message_id, s.original_delete_policy
+            FROM starred_messages sm
+            JOIN starboards s ON s.channel_id = sm.starboard_channel_id
+            WHERE sm.original_message_id = ?1",
+            message_id
+        )
+        .fetch_all(data.database.pool())
+        .await?;
+
+        for message_starboard_entry in starboard_entries_for_message {
+            // The original message no longer exists so the entry can never be updated again.
+            // The entry is removed first so the deletion isn't mistaken for a moderator removal.
+            query!(
+                "DELETE FROM starred_messages WHERE starboard_message_id = ?1",
+                message_starboard_entry.starboard_message_id
+            )
+            .execute(data.database.pool())
+            .await?;
+
+            let starboard_channel =
+                ChannelId::new(message_starboard_entry.starboard_channel_id.try_into()?);
+            let starboard_message =
+                MessageId::new(message_starboard_entry.starboard_message_id.try_into()?);
+            match DeletePolicy::from_stored(&message_starboard_entry.original_delete_policy) {
+                DeletePolicy::Delete => {
+                    if let Err(err) = ctx
+                        .http
+                        .delete_message(starboard_channel, starboard_message, None)
+                        .await
+                    {
+                        warn!(
+                            starboard_message_id = message_starboard_entry.starboard_message_id,
+                            "Failed to delete starboard message on original message delete: {err:?}"
+                        );
+                    }
+                }
+                DeletePolicy::Archive => {
+                    // The original can no longer be fetched, so only the link to it is replaced.
+                    if let Err(err) = starboard_channel
+                        .edit_message(
+                            &ctx.http,
+                            starboard_message,
+                            EditMessage::new().content("*The original message was deleted.*"),
+                        )
+                        .await
+                    {
+                        warn!(
+                            starboard_message_id = message_starboard_entry.starboard_message_id,
+                            "Failed to archive starboard message on original message delete: {err:?}"
+                        );
+                    }
+                }
+                DeletePolicy::Keep => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::{AppState, emoji::StarboardEmoji, events::starboard_message::make_starboard_message};
+use ::serenity::all::{ChannelId, EditMessage, MessageFlags, MessageId, MessageUpdateEvent};
+use anyhow::{Error, Result};
+use poise::serenity_prelude as serenity;
+use sqlx::query;
+use tracing::{debug, error, warn};
+
+pub async fn starboard_process_message_update(
+    ctx: &serenity::Context,
+    _framework: poise::FrameworkContext<'_, AppState, Error>,
+    data: &AppState,
+    event: &MessageUpdateEvent,
+) -> Result<()> {
+    // Find all starboard entries for the edited message.
+    let message_id: i64 = event.id.get().try_into()?;
+    let starboard_entries = query!(
+        "SELECT sm.starboard_message_id, sm.starboard_channel_id, sm.react_count, sm.veto_count, s.threshold, s.mark_edits
+        FROM starred_messages sm
+        JOIN starboards s ON s.channel_id = sm.starboard_channel_id
+        WHERE sm.original_message_id = ?1 AND sm.frozen = FALSE AND s.deleted_at IS NULL",
+        message_id
+    )
+    .fetch_all(data.database.pool())
+    .await?;
+    if starboard_entries.is_empty() {
+        return Ok(());
+    }
+
+    // Fetch the full message as update events only contain the changed fields.
+    let message = match event.channel_id.message(&ctx.http, event.id).await {
+        Ok(message) => message,
+        Err(e) => {
+            error!("Failed to get message from update event: {:?}", e);
+            return Ok(());
+        }
+    };
+
+    for entry in starboard_entries {
+        let Some(emoji) = query!(
+            "SELECT emoji FROM starboard_emojis WHERE starboard_channel_id = ?1 ORDER BY rowid LIMIT 1",
+            entry.starboard_channel_id
+        )
+        .fetch_optional(data.database.pool())
+        .await?
+        .map(|row| StarboardEmoji::from_stored(&row.emoji)) else {
+            continue;
+        };
+
+        // Re-render the starboard message with the edited content.
+        let message_parts = make_starboard_message(
+            &message,
+            &emoji,
+            entry.react_count.try_into()?,
+            entry.veto_count.try_into()?,
+            entry.threshold.try_into()?,
+            entry.mark_edits,
+        );
+        if let Err(err) = ChannelId::new(entry.starboard_channel_id.try_into()?)
+            .edit_message(
+                &ctx.http,
+                MessageId::new(entry.starboard_message_id.try_into()?),
+                EditMessage::new()
+                    .content(message_parts.content)
+                    .embed(message_parts.embed)
+                    .flags(MessageFlags::SUPPRESS_NOTIFICATIONS),
+            )
+            .await
+        {
+            warn!(
+                starboard_message_id = entry.starboard_message_id,
+                "Failed to update starboard message on message edit: {err:?}"
+            );
+            continue;
+        }
+        debug!(
+            message_id = %event.id.get(),
+            starboard_channel_id = %entry.starboard_channel_id,
+            "synced starboard message with edited original",
+        );
+    }
+
+    Ok(())
+}
@@ -1,10 +1,16 @@
+mod message;
 mod reaction;
+pub mod starboard_message;
+
+pub use self::reaction::{
+    Verdict, delete_entry, evaluate_entry, evaluate_entry_with_emojis, reconcile_entry,
+};
 
 use crate::{
     AppState,
-    events::reaction::{
-        starboard_process_react_add, starboard_process_react_remove,
-        starboard_process_react_remove_all,
+    events::{
+        message::{starboard_process_message_delete, starboard_process_message_update},
+        reaction::{starboard_process_react, starboard_process_react_remove_all},
     },
 };
 use anyhow::{Error, Result};
@@ -22,10 +28,10 @@ pub async fn event_handler(
             info!("Logged in as {}", data_about_bot.user.name);
         }
         FullEvent::ReactionAdd { add_reaction } => {
-            starboard_process_react_add(ctx, framework, data, add_reaction).await?;
+            starboard_process_react(ctx, framework, data, add_reaction).await?;
         }
         FullEvent::ReactionRemove { removed_reaction } => {
-            starboard_process_react_remove(ctx, framework, data, removed_reaction).await?;
+            starboard_process_react(ctx, framework, data, removed_reaction).await?;
         }
         FullEvent::ReactionRemoveAll {
             channel_id,
@@ -40,6 +46,21 @@ pub async fn event_handler(
             )
             .await?;
         }
+        FullEvent::MessageDelete {
+            deleted_message_id, ..
+        } => {
+            starboard_process_message_delete(ctx, framework, data, &[*deleted_message_id]).await?;
+        }
+        FullEvent::MessageDeleteBulk {
+            multiple_deleted_messages_ids,
+            ..
+        } => {
+            starboard_process_message_delete(ctx, framework, data, multiple_deleted_messages_ids)
+                .await?;
+        }
+        FullEvent::MessageUpdate { event, .. } => {
+            starboard_process_message_update(ctx, framework, data, event).await?;
+        }
         _ => {}
     }
     Ok(())
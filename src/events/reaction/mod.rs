@@ -1,193 +1,215 @@
-mod starboard_react_add;
-mod starboard_react_remove;
+mod reconcile;
+mod starboard_react;
 mod starboard_react_remove_all;
-pub use starboard_react_add::starboard_process_react_add;
-pub use starboard_react_remove::starboard_process_react_remove;
+mod visibility;
+pub use reconcile::{
+    Verdict, delete_entry, evaluate_entry, evaluate_entry_with_emojis, reconcile_entry,
+};
+pub use starboard_react::starboard_process_react;
 pub use starboard_react_remove_all::starboard_process_react_remove_all;
+pub use visibility::role_can_view_channel;
 
+use crate::{database::DatabasePool, emoji::StarboardEmoji};
 use anyhow::Result;
-use linkify::LinkFinder;
 use serenity::all::{
-    Colour, CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, Http, Message, ReactionType, User,
+    Channel, ChannelId, Context, GuildId, Http, Message, RoleId, Timestamp, User, UserId,
 };
+use sqlx::query;
+use std::collections::HashSet;
 use tracing::warn;
-use url::Url;
 
-async fn count_reactors<F>(
-    message: &Message,
+/// Find the unique users that reacted to any of the given messages with any of the given emojis.
+pub async fn find_reactors<F>(
+    messages: &[&Message],
     http: impl AsRef<Http>,
-    emoji: &ReactionType,
+    emojis: &[StarboardEmoji],
     filter: F,
-) -> Result<usize>
+) -> Result<HashSet<UserId>>
 where
     F: Fn(&User) -> bool,
 {
-    let mut reactors_count = 0;
-    let mut after = None;
-    loop {
-        match message
-            .reaction_users(&http, emoji.clone(), Some(100), after)
-            .await
-        {
-            Ok(reactors) => {
-                let len = reactors.len();
-                reactors_count += reactors.iter().filter(|r| filter(r)).count();
-                if len < 100 {
-                    break;
+    let mut reactors = HashSet::new();
+    for message in messages {
+        for emoji in emojis {
+            // Use the reaction as it appears on the message so custom emojis are requested with their current name.
+            let Some(emoji) = message
+                .reactions
+                .iter()
+                .find(|r| emoji.matches(&r.reaction_type))
+                .map(|r| r.reaction_type.clone())
+            else {
+                continue;
+            };
+
+            let mut after = None;
+            loop {
+                match message
+                    .reaction_users(&http, emoji.clone(), Some(100), after)
+                    .await
+                {
+                    Ok(users) => {
+                        let len = users.len();
+                        reactors.extend(users.iter().filter(|r| filter(r)).map(|r| r.id));
+                        if len < 100 {
+                            break;
+                        }
+                        after = users.last().map(|u| u.id);
+                    }
+                    Err(e) => {
+                        warn!("Unable to get reactors for message {}: {}", message.id, e);
+                        return Err(e.into());
+                    }
                 }
-                after = reactors.last().map(|u| u.id);
-            }
-            Err(e) => {
-                warn!("Unable to get reactors for message {}: {}", message.id, e);
-                return Err(e.into());
             }
         }
     }
-    Ok(reactors_count)
+    Ok(reactors)
 }
 
-struct StarboardMessageParts {
-    pub content: String,
-    pub embed: CreateEmbed,
+/// The most users that could have reacted to any of the given messages with any of the given emojis.
+///
+/// Uses the reaction counts Discord includes with messages, so no requests are needed. The real number of
+/// reactors can only be lower, as users reacting with several emojis or on several messages count once.
+pub fn max_reaction_count(messages: &[&Message], emojis: &[StarboardEmoji]) -> i64 {
+    messages
+        .iter()
+        .flat_map(|message| &message.reactions)
+        .filter(|r| emojis.iter().any(|emoji| emoji.matches(&r.reaction_type)))
+        .map(|r| i64::try_from(r.count).unwrap_or(i64::MAX))
+        .fold(0, i64::saturating_add)
 }
 
-/// Get the components needed to create a starboard message.
-fn make_starboard_message(
-    original_message: &Message,
-    emoji: String,
-    react_count: usize,
-    reacts_needed: usize,
-) -> StarboardMessageParts {
-    StarboardMessageParts {
-        embed: make_starboard_embed(original_message, &emoji, react_count, reacts_needed),
-        content: original_message.link(),
+/// Score the given reactors according to the member rules of a starboard.
+///
+/// Reactors that joined the guild less than `min_member_age` seconds ago are ignored. Without role rules
+/// every other reactor counts once. Otherwise reactors must have one of the required roles (if there are any)
+/// and none of the excluded roles, and count as the highest weight among their roles.
+pub async fn score_reactors(
+    ctx: &Context,
+    pool: &DatabasePool,
+    guild_id: GuildId,
+    starboard_channel_id: i64,
+    min_member_age: i64,
+    reactors: &HashSet<UserId>,
+) -> Result<usize> {
+    let role_rules = query!(
+        "SELECT role_id, required, excluded, weight FROM starboard_role_rules WHERE starboard_channel_id = ?1",
+        starboard_channel_id
+    )
+    .fetch_all(pool)
+    .await?;
+    if role_rules.is_empty() && min_member_age <= 0 {
+        return Ok(reactors.len());
     }
-}
 
-/// Creates an embed for a starboard message.
-fn make_starboard_embed(
-    message: &Message,
-    emoji: &str,
-    react_count: usize,
-    reacts_needed: usize,
-) -> CreateEmbed {
-    let mut embed = CreateEmbed::default()
-        .author(CreateEmbedAuthor::new(&message.author.name).icon_url(message.author.face()))
-        .footer(CreateEmbedFooter::new(format!(
-            "{} {}  • {}",
-            emoji, react_count, message.id
-        )))
-        .timestamp(message.timestamp)
-        .colour(match react_count {
-            count if count < reacts_needed * 2 => Colour::DARK_ORANGE, // between minimum and 2x
-            count if count < reacts_needed * 3 => Colour::ORANGE, // between 2x to 3x of minimum,
-            _ => Colour::GOLD,                                    // 3x or higher of minimum
-        });
+    let now = Timestamp::now().unix_timestamp();
+    let has_required_roles = role_rules.iter().any(|r| r.required);
+    let mut score = 0;
+    for user_id in reactors {
+        // Users that are no longer in the guild are treated as having no roles.
+        let member = match guild_id.member(ctx, *user_id).await {
+            Ok(member) => Some(member),
+            Err(e) => {
+                warn!("Unable to get member {} for member rules: {}", user_id, e);
+                None
+            }
+        };
+        if min_member_age > 0
+            && member
+                .as_ref()
+                .and_then(|m| m.joined_at)
+                .is_none_or(|joined_at| now - joined_at.unix_timestamp() < min_member_age)
+        {
+            continue;
+        }
 
-    // Add message content
-    if !message.content.is_empty() {
-        embed = embed.description(&message.content);
-    }
+        let roles = member.map(|m| m.roles).unwrap_or_default();
+        let mut matching_rules = Vec::new();
+        for rule in &role_rules {
+            if roles.contains(&RoleId::new(rule.role_id.try_into()?)) {
+                matching_rules.push(rule);
+            }
+        }
 
-    // Add the first attachment as an image or add a field with all attachments.
-    if let Some(attachment) = message.attachments.first() {
-        let content_type = attachment.content_type.as_deref().unwrap_or("");
-        embed = if content_type.starts_with("image") {
-            embed.image(&attachment.url)
-        } else {
-            embed.field(
-                "Attachments",
-                message
-                    .attachments
-                    .iter()
-                    .map(|a| format!("[{}]({})", a.filename, a.url))
-                    .collect::<Vec<_>>()
-                    .join("\n"),
-                false,
-            )
+        if has_required_roles && !matching_rules.iter().any(|r| r.required) {
+            continue;
+        }
+        if matching_rules.iter().any(|r| r.excluded) {
+            continue;
+        }
+        score += match matching_rules.iter().filter_map(|r| r.weight).max() {
+            Some(weight) => usize::try_from(weight)?,
+            None => 1,
         };
     }
-    // Embed the first image URL found in the message content.
-    else if let Some(link) =
-        find_all_image_urls_in_str(&message.content).and_then(|links| links.into_iter().next())
-    {
-        embed = embed.image(link.to_string());
-    }
-    // Embed the first image from an embed on the message.
-    else if let Some(image_url) = message
-        .embeds
-        .iter()
-        .find_map(|e| e.image.as_ref().map(|img| &img.url))
-    {
-        embed = embed.image(image_url);
-    }
+    Ok(score)
+}
 
-    // Add reply context
-    if let Some(reply) = &message.referenced_message {
-        const MAX_REPLY_CHARS: usize = 524;
+/// Whether messages from the given channel can be posted to a starboard according to its channel rules.
+///
+/// The most specific rule for the channel, its parent channel or its category is used. When no rule matches
+/// the channel is only allowed if the starboard doesn't include specific channels.
+async fn source_channel_allowed(
+    ctx: &Context,
+    pool: &DatabasePool,
+    starboard_channel_id: i64,
+    channel_id: ChannelId,
+) -> Result<bool> {
+    let filters = query!(
+        "SELECT channel_id, include FROM starboard_channel_filters WHERE starboard_channel_id = ?1",
+        starboard_channel_id
+    )
+    .fetch_all(pool)
+    .await?;
+    if filters.is_empty() {
+        return Ok(true);
+    }
 
-        let reply_content = if reply.content.is_empty() {
-            "*sent an attachment, embed or sticker.*".to_string()
-        } else if reply.content.chars().count() > MAX_REPLY_CHARS {
-            reply
-                .content
-                .chars()
-                .take(MAX_REPLY_CHARS)
-                .chain("...".chars())
-                .collect::<String>()
-        } else {
-            reply.content.clone()
+    // Walk up from the channel through thread parents and categories.
+    let mut current = Some(channel_id);
+    while let Some(channel_id) = current {
+        let id: i64 = channel_id.get().try_into()?;
+        if let Some(filter) = filters.iter().find(|f| f.channel_id == id) {
+            return Ok(filter.include);
+        }
+        current = match channel_id.to_channel(ctx).await {
+            Ok(Channel::Guild(channel)) => channel.parent_id,
+            _ => None,
         };
-
-        embed = embed.field(
-            format!("In reply to {}'s message", reply.author.name),
-            reply_content,
-            false,
-        );
     }
 
-    embed
+    Ok(!filters.iter().any(|f| f.include))
 }
 
-/// Returns a vec with all complete image links inside of the given string.
-///
-/// File types are checked case-insensitively.
-///
-/// ## Media Support
-/// Media support makes a best effort to mirror the formats that Discord can use inside of an image field in an embed.
-///
-/// * `jpg`
-/// * `jpeg`
-/// * `png`
-/// * `gif`
-fn find_all_image_urls_in_str(s: &str) -> Option<Vec<Url>> {
-    if s.is_empty() {
-        return None;
-    }
-
-    let links: Vec<_> = LinkFinder::new().links(s).collect();
-    if links.is_empty() {
-        return None;
+/// Whether the given channel is age-restricted, using the parent channel for threads.
+async fn channel_is_nsfw(ctx: &Context, channel_id: ChannelId) -> Result<bool> {
+    let Channel::Guild(channel) = channel_id.to_channel(ctx).await? else {
+        return Ok(false);
+    };
+    match (channel.thread_metadata.is_some(), channel.parent_id) {
+        (true, Some(parent_id)) => match parent_id.to_channel(ctx).await? {
+            Channel::Guild(parent) => Ok(parent.nsfw),
+            _ => Ok(false),
+        },
+        _ => Ok(channel.nsfw),
     }
+}
 
-    let image_urls: Vec<Url> = links
+/// How many lower tiers are above the given starboard among the given `(channel_id, parent_starboard_id)` pairs.
+fn tier_depth(tiers: &[(i64, Option<i64>)], channel_id: i64) -> usize {
+    let mut depth = 0;
+    let mut current = channel_id;
+    while let Some(parent) = tiers
         .iter()
-        .filter_map(|link| {
-            let link_str = link.as_str().to_lowercase();
-            if !link_str.contains(".jpg")
-                && !link_str.contains(".jpeg")
-                && !link_str.contains(".png")
-                && !link_str.contains(".gif")
-            {
-                return None;
-            }
-            Url::parse(link.as_str()).ok()
-        })
-        .collect();
-
-    if image_urls.is_empty() {
-        None
-    } else {
-        Some(image_urls)
+        .find(|(id, _)| *id == current)
+        .and_then(|(_, parent)| *parent)
+    {
+        // Guard against cycles that might have been stored.
+        if depth >= tiers.len() {
+            break;
+        }
+        depth += 1;
+        current = parent;
     }
+    depth
 }
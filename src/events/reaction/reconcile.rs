@@ -0,0 +1,761 @@
+use super::{
+    channel_is_nsfw, find_reactors, max_reaction_count, role_can_view_channel, score_reactors,
+    source_channel_allowed,
+};
+use crate::{
+    AppState, database::DatabasePool, emoji::StarboardEmoji, entry_locks::EntryLocks,
+    events::starboard_message::make_starboard_message,
+};
+use anyhow::Result;
+use serenity::all::{
+    ChannelId, Context, CreateMessage, EditMessage, GuildId, Message, MessageFlags, MessageId,
+    RoleId, StatusCode, Timestamp, User,
+};
+use sqlx::query;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{debug, warn};
+
+/// The state the entry of a message on a starboard should be in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// The entry should be left as it is, for the given reason.
+    Skipped(&'static str),
+    /// The message should not be on the starboard.
+    ///
+    /// The count is only an upper bound when `estimated`, as reactors weren't listed.
+    Absent {
+        react_count: i64,
+        threshold: i64,
+        estimated: bool,
+    },
+    /// The message should be on the starboard.
+    Present { react_count: i64, threshold: i64 },
+}
+
+/// The entry a message should have on a starboard, worked out without changing anything.
+#[derive(Debug)]
+pub struct Evaluation {
+    pub verdict: Verdict,
+    /// The starboard message currently posted for the message, if any.
+    pub starboard_message_id: Option<i64>,
+    /// Whether the message author reacted to their own message and wasn't counted.
+    pub selfstar_excluded: bool,
+    /// Whether the starboard message was deleted by someone other than the bot.
+    post_removed: bool,
+    entry: Option<PlannedEntry>,
+}
+
+/// What's needed to post or edit an entry that should be present.
+#[derive(Debug)]
+struct PlannedEntry {
+    emoji: StarboardEmoji,
+    existing_post: Option<Message>,
+    veto_count: i64,
+    forced: bool,
+    mark_edits: bool,
+    parent_starboard_id: Option<i64>,
+}
+
+impl Evaluation {
+    fn skipped(starboard_message_id: Option<i64>, reason: &'static str) -> Self {
+        Self {
+            verdict: Verdict::Skipped(reason),
+            starboard_message_id,
+            selfstar_excluded: false,
+            post_removed: false,
+            entry: None,
+        }
+    }
+}
+
+/// Converge the entry of a message on a starboard with the message's current reactions.
+///
+/// Posts, edits or deletes the starboard message and its `starred_messages` row to match what
+/// [`evaluate_entry`] decides, returning that decision.
+pub async fn reconcile_entry(
+    ctx: &Context,
+    data: &AppState,
+    starboard_channel_id: i64,
+    original_message: &Message,
+    force: bool,
+) -> Result<Verdict> {
+    let original_message_id: i64 = original_message.id.get().try_into()?;
+    let entry = LiveEntry {
+        ctx,
+        data,
+        starboard_channel_id,
+        original_message,
+        original_message_id,
+        force,
+    };
+    reconcile_locked(
+        &data.entry_locks,
+        starboard_channel_id,
+        original_message_id,
+        &entry,
+    )
+    .await
+}
+
+/// The steps of reconciling an entry, kept apart from the locking around them so it can be tested.
+trait EntryReconciler {
+    type Plan;
+
+    /// Work out what the entry should be without changing anything.
+    async fn evaluate(&self) -> Result<Self::Plan>;
+
+    /// Change the entry to match what was worked out.
+    async fn apply(&self, plan: Self::Plan) -> Result<Verdict>;
+}
+
+/// Evaluate and apply an entry while holding its lock.
+async fn reconcile_locked<R: EntryReconciler>(
+    locks: &EntryLocks,
+    starboard_channel_id: i64,
+    original_message_id: i64,
+    entry: &R,
+) -> Result<Verdict> {
+    // Events for the same entry are handled one at a time so they don't both post a new message.
+    let _entry_lock = locks.lock(starboard_channel_id, original_message_id).await;
+    let plan = entry.evaluate().await?;
+    entry.apply(plan).await
+}
+
+/// An entry reconciled against Discord and the database.
+struct LiveEntry<'a> {
+    ctx: &'a Context,
+    data: &'a AppState,
+    starboard_channel_id: i64,
+    original_message: &'a Message,
+    original_message_id: i64,
+    force: bool,
+}
+
+impl EntryReconciler for LiveEntry<'_> {
+    type Plan = Evaluation;
+
+    async fn evaluate(&self) -> Result<Evaluation> {
+        evaluate_entry(
+            self.ctx,
+            self.data.database.pool(),
+            self.starboard_channel_id,
+            self.original_message,
+            self.force,
+        )
+        .await
+    }
+
+    async fn apply(&self, evaluation: Evaluation) -> Result<Verdict> {
+        let Self {
+            ctx,
+            data,
+            starboard_channel_id,
+            original_message,
+            original_message_id,
+            ..
+        } = *self;
+        let pool = data.database.pool();
+        let (react_count, threshold) = match evaluation.verdict {
+            // The starboard message was removed without the bot noticing, so treat it as a moderator removal.
+            Verdict::Skipped(_) if evaluation.post_removed => {
+                if let Some(starboard_message_id) = evaluation.starboard_message_id {
+                    query!(
+                        "INSERT OR IGNORE INTO blocked_messages (starboard_channel_id, original_message_id) VALUES (?1, ?2)",
+                        starboard_channel_id,
+                        original_message_id
+                    )
+                    .execute(pool)
+                    .await?;
+                    query!(
+                        "DELETE FROM starred_messages WHERE starboard_message_id = ?1",
+                        starboard_message_id
+                    )
+                    .execute(pool)
+                    .await?;
+                    debug!(
+                        original_message_id = %original_message_id,
+                        starboard_channel_id = %starboard_channel_id,
+                        "blocked message - starboard message was removed",
+                    );
+                }
+                return Ok(evaluation.verdict);
+            }
+            Verdict::Skipped(reason) => {
+                debug!(
+                    message_id = %original_message_id,
+                    starboard_channel_id = %starboard_channel_id,
+                    "skip reconcile - {reason}",
+                );
+                return Ok(evaluation.verdict);
+            }
+            // Take the message off the starboard when it no longer meets the threshold.
+            Verdict::Absent { .. } => {
+                if let Some(starboard_message_id) = evaluation.starboard_message_id {
+                    delete_entry(ctx, pool, starboard_channel_id, starboard_message_id).await?;
+                }
+                return Ok(evaluation.verdict);
+            }
+            Verdict::Present {
+                react_count,
+                threshold,
+            } => (react_count, threshold),
+        };
+        let Some(entry) = evaluation.entry else {
+            return Ok(evaluation.verdict);
+        };
+
+        // Edit the existing starboard message or send a new one.
+        let message_parts = make_starboard_message(
+            original_message,
+            &entry.emoji,
+            react_count.try_into()?,
+            entry.veto_count.try_into()?,
+            threshold.try_into()?,
+            entry.mark_edits,
+        );
+        let starboard_message = match entry.existing_post {
+            Some(mut message) => {
+                message
+                    .edit(
+                        &ctx.http,
+                        EditMessage::new()
+                            .content(message_parts.content)
+                            .embed(message_parts.embed)
+                            .flags(MessageFlags::SUPPRESS_NOTIFICATIONS),
+                    )
+                    .await?;
+                message
+            }
+            None => {
+                ChannelId::new(starboard_channel_id.try_into()?)
+                    .send_message(
+                        &ctx.http,
+                        CreateMessage::new()
+                            .content(message_parts.content)
+                            .embed(message_parts.embed)
+                            .flags(MessageFlags::SUPPRESS_NOTIFICATIONS),
+                    )
+                    .await?
+            }
+        };
+
+        // Add/update the entry in the database.
+        let message_author_id: i64 = original_message.author.id.get().try_into()?;
+        let message_channel_id: i64 = original_message.channel_id.get().try_into()?;
+        let starboard_message_id: i64 = starboard_message.id.get().try_into()?;
+        query!(
+            "INSERT INTO starred_messages
+            (starboard_message_id, starboard_channel_id, original_message_id, original_message_author_id, original_message_channel_id, react_count, veto_count, forced) VALUES
+            (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ON CONFLICT (starboard_channel_id, original_message_id) DO UPDATE
+            SET starboard_message_id = ?1, react_count = ?6, veto_count = ?7, forced = ?8",
+            starboard_message_id,
+            starboard_channel_id,
+            original_message_id,
+            message_author_id,
+            message_channel_id,
+            react_count,
+            entry.veto_count,
+            entry.forced
+        )
+        .execute(pool)
+        .await?;
+
+        // Newly promoted messages can be removed from the lower tier.
+        if evaluation.starboard_message_id.is_none()
+            && let Some(parent_starboard_id) = entry.parent_starboard_id
+        {
+            remove_promoted_entry(ctx, data, parent_starboard_id, original_message_id).await?;
+        }
+
+        Ok(evaluation.verdict)
+    }
+}
+
+/// Work out whether a message belongs on a starboard and with what count, without changing anything.
+///
+/// Forcing ignores the starboard's rules and threshold, keeping the message on the starboard until it is
+/// removed by a moderator.
+pub async fn evaluate_entry(
+    ctx: &Context,
+    pool: &DatabasePool,
+    starboard_channel_id: i64,
+    original_message: &Message,
+    force: bool,
+) -> Result<Evaluation> {
+    evaluate(
+        ctx,
+        pool,
+        starboard_channel_id,
+        original_message,
+        force,
+        None,
+    )
+    .await
+}
+
+/// Work out where a message would stand on a starboard if it used the given 'star' emojis instead of its own.
+pub async fn evaluate_entry_with_emojis(
+    ctx: &Context,
+    pool: &DatabasePool,
+    starboard_channel_id: i64,
+    original_message: &Message,
+    emojis: &[StarboardEmoji],
+) -> Result<Evaluation> {
+    evaluate(
+        ctx,
+        pool,
+        starboard_channel_id,
+        original_message,
+        false,
+        Some(emojis),
+    )
+    .await
+}
+
+async fn evaluate(
+    ctx: &Context,
+    pool: &DatabasePool,
+    starboard_channel_id: i64,
+    original_message: &Message,
+    force: bool,
+    emojis: Option<&[StarboardEmoji]>,
+) -> Result<Evaluation> {
+    let original_message_id: i64 = original_message.id.get().try_into()?;
+    let Some(starboard) = query!(
+        "SELECT guild_id, enabled, allow_selfstar, threshold, mark_edits, count_starboard_reactions,
+            visibility_check, visibility_role_id, nsfw, min_account_age, min_member_age, veto_emoji,
+            max_message_age, parent_starboard_id, require_parent, remove_on_promotion
+        FROM starboards WHERE channel_id = ?1 AND deleted_at IS NULL",
+        starboard_channel_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(Evaluation::skipped(None, "starboard does not exist"));
+    };
+    let guild = GuildId::new(starboard.guild_id.try_into()?);
+    let starboard_channel = ChannelId::new(starboard_channel_id.try_into()?);
+
+    let existing_entry = query!(
+        "SELECT starboard_message_id, forced, frozen FROM starred_messages
+        WHERE starboard_channel_id = ?1 AND original_message_id = ?2",
+        starboard_channel_id,
+        original_message_id
+    )
+    .fetch_optional(pool)
+    .await?;
+    let starboard_message_id = existing_entry
+        .as_ref()
+        .map(|entry| entry.starboard_message_id);
+    let now = Timestamp::now().unix_timestamp();
+
+    // Starboard messages are never entries themselves, even when forced.
+    if original_message.channel_id == starboard_channel {
+        return Ok(Evaluation::skipped(
+            starboard_message_id,
+            "message is inside of the starboard",
+        ));
+    }
+
+    if !force {
+        // Ignore all events when the starboard is not enabled.
+        if !starboard.enabled {
+            return Ok(Evaluation::skipped(
+                starboard_message_id,
+                "starboard not enabled",
+            ));
+        }
+
+        // Ignore messages from channels that don't feed the starboard.
+        if !source_channel_allowed(ctx, pool, starboard_channel_id, original_message.channel_id)
+            .await?
+        {
+            return Ok(Evaluation::skipped(
+                starboard_message_id,
+                "channel not allowed",
+            ));
+        }
+
+        // Ignore messages from age-restricted channels unless the starboard is also age-restricted.
+        if !starboard.nsfw && channel_is_nsfw(ctx, original_message.channel_id).await? {
+            return Ok(Evaluation::skipped(
+                starboard_message_id,
+                "nsfw message on sfw starboard",
+            ));
+        }
+
+        // Ignore messages that would be shown to people who can't see the original channel.
+        if starboard.visibility_check {
+            let role_id = match starboard.visibility_role_id {
+                Some(role_id) => RoleId::new(role_id.try_into()?),
+                None => RoleId::new(guild.get()),
+            };
+            if role_can_view_channel(ctx, guild, role_id, starboard_channel).await?
+                && !role_can_view_channel(ctx, guild, role_id, original_message.channel_id).await?
+            {
+                return Ok(Evaluation::skipped(
+                    starboard_message_id,
+                    "original channel is more private than starboard",
+                ));
+            }
+        }
+
+        // Ignore messages that were removed from the starboard by a moderator.
+        if query!(
+            "SELECT original_message_id FROM blocked_messages
+            WHERE starboard_channel_id = ?1 AND original_message_id = ?2",
+            starboard_channel_id,
+            original_message_id
+        )
+        .fetch_optional(pool)
+        .await?
+        .is_some()
+        {
+            return Ok(Evaluation::skipped(
+                starboard_message_id,
+                "message blocked from starboard",
+            ));
+        }
+
+        // Ignore entries that have been frozen by a moderator.
+        if existing_entry.as_ref().is_some_and(|entry| entry.frozen) {
+            return Ok(Evaluation::skipped(
+                starboard_message_id,
+                "starboard entry frozen",
+            ));
+        }
+
+        // Higher tiers can require new entries to already be on the lower tier.
+        if let Some(parent_starboard_id) = starboard.parent_starboard_id
+            && starboard.require_parent
+            && existing_entry.is_none()
+            && query!(
+                "SELECT starboard_message_id FROM starred_messages
+                WHERE starboard_channel_id = ?1 AND original_message_id = ?2",
+                parent_starboard_id,
+                original_message_id
+            )
+            .fetch_optional(pool)
+            .await?
+            .is_none()
+        {
+            return Ok(Evaluation::skipped(
+                starboard_message_id,
+                "not on lower tier starboard",
+            ));
+        }
+
+        // Lower tiers that remove promoted entries don't take them back.
+        if starboard.remove_on_promotion
+            && query!(
+                "SELECT sm.starboard_message_id FROM starred_messages sm
+                JOIN starboards s ON s.channel_id = sm.starboard_channel_id
+                WHERE s.parent_starboard_id = ?1 AND sm.original_message_id = ?2",
+                starboard_channel_id,
+                original_message_id
+            )
+            .fetch_optional(pool)
+            .await?
+            .is_some()
+        {
+            return Ok(Evaluation::skipped(
+                starboard_message_id,
+                "promoted to higher tier starboard",
+            ));
+        }
+
+        // Ignore messages that are too old to become new entries, existing entries still update.
+        if existing_entry.is_none()
+            && starboard.max_message_age > 0
+            && now - original_message.timestamp.unix_timestamp() > starboard.max_message_age
+        {
+            return Ok(Evaluation::skipped(
+                starboard_message_id,
+                "message too old for new entries",
+            ));
+        }
+    }
+    let forced = force || existing_entry.as_ref().is_some_and(|entry| entry.forced);
+
+    // Get the emojis that count towards the starboard.
+    let emojis = match emojis {
+        Some(emojis) => emojis.to_vec(),
+        None => query!(
+            "SELECT emoji FROM starboard_emojis WHERE starboard_channel_id = ?1 ORDER BY rowid",
+            starboard_channel_id
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| StarboardEmoji::from_stored(&row.emoji))
+        .collect::<Vec<_>>(),
+    };
+    let Some(emoji) = emojis.first() else {
+        return Ok(Evaluation::skipped(
+            starboard_message_id,
+            "no star emojis configured",
+        ));
+    };
+
+    // Fetch the existing starboard message, which also counts reactions when enabled.
+    let existing_post = match &existing_entry {
+        Some(entry) => match starboard_channel
+            .message(
+                &ctx.http,
+                MessageId::new(entry.starboard_message_id.try_into()?),
+            )
+            .await
+        {
+            Ok(message) => Some(message),
+            // Forcing posts the message again, otherwise the removal is respected.
+            Err(serenity::Error::Http(err)) if err.status_code() == Some(StatusCode::NOT_FOUND) => {
+                if !force {
+                    return Ok(Evaluation {
+                        post_removed: true,
+                        ..Evaluation::skipped(starboard_message_id, "starboard message was removed")
+                    });
+                }
+                None
+            }
+            Err(err) => return Err(err.into()),
+        },
+        None => None,
+    };
+    let counted_messages = [
+        Some(original_message),
+        existing_post
+            .as_ref()
+            .filter(|_| starboard.count_starboard_reactions),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+
+    // Skip listing reactors when the reaction counts on the messages can't reach the threshold.
+    if !forced {
+        let max_weight = query!(
+            r#"SELECT MAX(weight) AS "max_weight: i64" FROM starboard_role_rules WHERE starboard_channel_id = ?1"#,
+            starboard_channel_id
+        )
+        .fetch_one(pool)
+        .await?
+        .max_weight
+        .unwrap_or(1)
+        .max(1);
+        let max_score = max_reaction_count(&counted_messages, &emojis).saturating_mul(max_weight);
+        if max_score < starboard.threshold {
+            debug!(
+                message_id = %original_message_id,
+                starboard_channel_id = %starboard_channel_id,
+                max_score = %max_score,
+                threshold = %starboard.threshold,
+                "evaluate - reaction counts below threshold",
+            );
+            return Ok(Evaluation {
+                verdict: Verdict::Absent {
+                    react_count: max_score,
+                    threshold: starboard.threshold,
+                    estimated: true,
+                },
+                starboard_message_id,
+                selfstar_excluded: false,
+                post_removed: false,
+                entry: None,
+            });
+        }
+    }
+
+    // Score the users that reacted to the message.
+    let reactor_filter = |r: &User| {
+        (starboard.allow_selfstar || r.id != original_message.author.id)
+            && !r.bot
+            && now - r.id.created_at().unix_timestamp() >= starboard.min_account_age
+    };
+    let author_starred = AtomicBool::new(false);
+    let star_filter = |r: &User| {
+        if r.id == original_message.author.id {
+            author_starred.store(true, Ordering::Relaxed);
+        }
+        reactor_filter(r)
+    };
+    let reactors = find_reactors(&counted_messages, &ctx.http, &emojis, star_filter).await?;
+    let star_count: i64 = score_reactors(
+        ctx,
+        pool,
+        guild,
+        starboard_channel_id,
+        starboard.min_member_age,
+        &reactors,
+    )
+    .await?
+    .try_into()?;
+
+    // Subtract users that reacted with the veto emoji from the score.
+    let veto_count: i64 = match &starboard.veto_emoji {
+        Some(veto_emoji) => {
+            let vetoers = find_reactors(
+                &counted_messages,
+                &ctx.http,
+                &[StarboardEmoji::from_stored(veto_emoji)],
+                reactor_filter,
+            )
+            .await?;
+            score_reactors(
+                ctx,
+                pool,
+                guild,
+                starboard_channel_id,
+                starboard.min_member_age,
+                &vetoers,
+            )
+            .await?
+            .try_into()?
+        }
+        None => 0,
+    };
+    let react_count = (star_count - veto_count).max(0);
+
+    let verdict = match react_count < starboard.threshold && !forced {
+        true => Verdict::Absent {
+            react_count,
+            threshold: starboard.threshold,
+            estimated: false,
+        },
+        false => Verdict::Present {
+            react_count,
+            threshold: starboard.threshold,
+        },
+    };
+    Ok(Evaluation {
+        verdict,
+        starboard_message_id,
+        selfstar_excluded: author_starred.load(Ordering::Relaxed) && !starboard.allow_selfstar,
+        post_removed: false,
+        entry: Some(PlannedEntry {
+            emoji: emoji.clone(),
+            existing_post,
+            veto_count,
+            forced,
+            mark_edits: starboard.mark_edits,
+            parent_starboard_id: starboard.parent_starboard_id,
+        }),
+    })
+}
+
+/// Remove an entry from a starboard without blocking the message from being posted again.
+///
+/// The entry is removed before the starboard message so the deletion isn't mistaken for a moderator removal.
+pub async fn delete_entry(
+    ctx: &Context,
+    pool: &DatabasePool,
+    starboard_channel_id: i64,
+    starboard_message_id: i64,
+) -> Result<()> {
+    query!(
+        "DELETE FROM starred_messages WHERE starboard_message_id = ?1",
+        starboard_message_id
+    )
+    .execute(pool)
+    .await?;
+    if let Err(err) = ctx
+        .http
+        .delete_message(
+            ChannelId::new(starboard_channel_id.try_into()?),
+            MessageId::new(starboard_message_id.try_into()?),
+            None,
+        )
+        .await
+    {
+        warn!(
+            starboard_message_id = starboard_message_id,
+            "Failed to delete starboard message: {err:?}"
+        );
+    }
+    Ok(())
+}
+
+/// Remove a message from a lower tier starboard after it was promoted, if that starboard is configured to.
+async fn remove_promoted_entry(
+    ctx: &Context,
+    data: &AppState,
+    starboard_channel_id: i64,
+    original_message_id: i64,
+) -> Result<()> {
+    let pool = data.database.pool();
+    let _entry_lock = data
+        .entry_locks
+        .lock(starboard_channel_id, original_message_id)
+        .await;
+    let Some(entry) = query!(
+        "SELECT sm.starboard_message_id FROM starred_messages sm
+        JOIN starboards s ON s.channel_id = sm.starboard_channel_id
+        WHERE sm.starboard_channel_id = ?1 AND sm.original_message_id = ?2
+            AND s.remove_on_promotion = TRUE AND sm.forced = FALSE",
+        starboard_channel_id,
+        original_message_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(());
+    };
+    delete_entry(ctx, pool, starboard_channel_id, entry.starboard_message_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EntryReconciler, Verdict, reconcile_locked};
+    use crate::entry_locks::EntryLocks;
+    use anyhow::Result;
+    use std::sync::{Arc, Mutex};
+    use tokio::task::{JoinSet, yield_now};
+
+    /// A stand-in for Discord and the database that records every starboard post, without any locking of its own.
+    #[derive(Default)]
+    struct MockEntry {
+        posts: Mutex<Vec<i64>>,
+        entry: Mutex<Option<usize>>,
+    }
+
+    impl EntryReconciler for MockEntry {
+        type Plan = Option<usize>;
+
+        async fn evaluate(&self) -> Result<Option<usize>> {
+            let existing = *self.entry.lock().unwrap();
+            for _ in 0..10 {
+                yield_now().await;
+            }
+            Ok(existing)
+        }
+
+        async fn apply(&self, existing: Option<usize>) -> Result<Verdict> {
+            if existing.is_none() {
+                let mut posts = self.posts.lock().unwrap();
+                posts.push(10);
+                *self.entry.lock().unwrap() = Some(posts.len() - 1);
+            }
+            Ok(Verdict::Present {
+                react_count: 1,
+                threshold: 1,
+            })
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_reactions_post_once() {
+        let locks = Arc::new(EntryLocks::default());
+        let entry = Arc::new(MockEntry::default());
+        let mut tasks = JoinSet::new();
+        for _ in 0..32 {
+            let locks = locks.clone();
+            let entry = entry.clone();
+            tasks.spawn(async move { reconcile_locked(&locks, 1, 10, &*entry).await });
+        }
+        for result in tasks.join_all().await {
+            result.unwrap();
+        }
+
+        assert_eq!(*entry.posts.lock().unwrap(), vec![10]);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{
+    AppState,
+    emoji::StarboardEmoji,
+    events::reaction::{reconcile_entry, tier_depth},
+};
+use ::serenity::all::{ChannelId, MessageId};
+use anyhow::{Error, Result};
+use poise::serenity_prelude as serenity;
+use serenity::all::Reaction;
+use sqlx::query;
+use tracing::{debug, error};
+
+/// Reconcile the starboards affected by a reaction being added or removed.
+pub async fn starboard_process_react(
+    ctx: &serenity::Context,
+    _framework: poise::FrameworkContext<'_, AppState, Error>,
+    data: &AppState,
+    reaction: &Reaction,
+) -> Result<()> {
+    // Events that do not occur in guilds are ignored.
+    let Some(guild) = reaction.guild_id else {
+        return Ok(());
+    };
+
+    // Since the bot does not work with super reacts we can skip events that contain them.
+    let Some(emoji) = StarboardEmoji::from_reaction(&reaction.emoji) else {
+        return Ok(());
+    };
+    if reaction.burst {
+        return Ok(());
+    }
+
+    // Drop reactions that no enabled starboard in the guild uses before doing any other work.
+    let guild_starboards = data
+        .config_cache
+        .starboards(data.database.pool(), guild)
+        .await?;
+    if !guild_starboards
+        .iter()
+        .any(|s| s.enabled && s.uses_emoji(&emoji))
+    {
+        return Ok(());
+    }
+
+    // Wait for the burst of reactions on the message to settle before counting them.
+    let Some(emojis) = data
+        .reaction_debouncer
+        .coalesce(reaction.message_id, emoji)
+        .await
+    else {
+        debug!(
+            message_id = %reaction.message_id.get(),
+            "skip react - coalesced into pending update",
+        );
+        return Ok(());
+    };
+
+    // Find applicable starboards for the reacted emojis.
+    let mut starboards = guild_starboards
+        .iter()
+        .filter(|s| s.enabled && emojis.iter().any(|emoji| s.uses_emoji(emoji)))
+        .collect::<Vec<_>>();
+
+    // Fetch the message that was reacted to.
+    let message = match reaction.message(&ctx.http).await {
+        Ok(message) => message,
+        Err(e) => {
+            error!("Failed to get message from react event: {:?}", e);
+            return Ok(());
+        }
+    };
+    let message_id: i64 = message.id.get().try_into()?;
+
+    // Process lower tiers first so promotions in the same event can see their entries.
+    let tiers = starboards
+        .iter()
+        .map(|s| (s.channel_id, s.parent_starboard_id))
+        .collect::<Vec<_>>();
+    starboards.sort_by_key(|s| tier_depth(&tiers, s.channel_id));
+
+    for starboard in starboards {
+        // Reactions inside of the starboard channel only count towards the original message when enabled.
+        let original_message_fetched;
+        let original_message = if i64::try_from(reaction.channel_id.get())? == starboard.channel_id
+        {
+            if !starboard.count_starboard_reactions {
+                debug!(
+                    message_id = %reaction.message_id.get(),
+                    starboard_channel_id = %starboard.channel_id,
+                    "skip react - inside of starboard channel",
+                );
+                continue;
+            }
+            let Some(original) = query!(
+                "SELECT original_message_id, original_message_channel_id FROM starred_messages
+                WHERE starboard_message_id = ?1",
+                message_id
+            )
+            .fetch_optional(data.database.pool())
+            .await?
+            else {
+                debug!(
+                    message_id = %reaction.message_id.get(),
+                    starboard_channel_id = %starboard.channel_id,
+                    "skip react - not a starboard message",
+                );
+                continue;
+            };
+            original_message_fetched =
+                match ChannelId::new(original.original_message_channel_id.try_into()?)
+                    .message(
+                        &ctx.http,
+                        MessageId::new(original.original_message_id.try_into()?),
+                    )
+                    .await
+                {
+                    Ok(message) => message,
+                    Err(e) => {
+                        error!(
+                            "Failed to get original message for starboard message: {:?}",
+                            e
+                        );
+                        continue;
+                    }
+                };
+            &original_message_fetched
+        } else {
+            &message
+        };
+
+        reconcile_entry(ctx, data, starboard.channel_id, original_message, false).await?;
+    }
+
+    Ok(())
+}
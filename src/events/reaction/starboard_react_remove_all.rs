@@ -1,45 +1,78 @@
-use crate::AppState;
+use crate::{AppState, events::reaction::reconcile_entry};
 use ::serenity::all::{ChannelId, MessageId};
 use anyhow::{Error, Result};
 use poise::serenity_prelude as serenity;
 use sqlx::query;
-use tracing::warn;
+use tracing::error;
 
 pub async fn starboard_process_react_remove_all(
     ctx: &serenity::Context,
     _framework: poise::FrameworkContext<'_, AppState, Error>,
     data: &AppState,
-    _channel_id: &ChannelId,
+    channel_id: &ChannelId,
     removed_from_message_id: &MessageId,
 ) -> Result<()> {
     let message_id: i64 = removed_from_message_id.get().try_into()?;
+
+    // Clearing the reactions on a starboard message lowers the count of its original when they're counted.
+    if let Some(entry) = query!(
+        "SELECT sm.starboard_channel_id, sm.original_message_id, sm.original_message_channel_id
+        FROM starred_messages sm
+        JOIN starboards s ON s.channel_id = sm.starboard_channel_id
+        WHERE sm.starboard_message_id = ?1 AND s.count_starboard_reactions = TRUE AND s.deleted_at IS NULL",
+        message_id
+    )
+    .fetch_optional(data.database.pool())
+    .await?
+    {
+        let original = match ChannelId::new(entry.original_message_channel_id.try_into()?)
+            .message(
+                &ctx.http,
+                MessageId::new(entry.original_message_id.try_into()?),
+            )
+            .await
+        {
+            Ok(message) => message,
+            Err(e) => {
+                error!(
+                    "Failed to get original message from react remove all event: {:?}",
+                    e
+                );
+                return Ok(());
+            }
+        };
+        reconcile_entry(ctx, data, entry.starboard_channel_id, &original, false).await?;
+        return Ok(());
+    }
+
     let starboard_entries_for_message = query!(
-        "SELECT starboard_channel_id, starboard_message_id FROM starred_messages WHERE original_message_id = ?1",
+        "SELECT starboard_channel_id FROM starred_messages WHERE original_message_id = ?1",
         message_id
     )
     .fetch_all(data.database.pool())
     .await?;
+    if starboard_entries_for_message.is_empty() {
+        return Ok(());
+    }
 
-    for message_starboard_entry in starboard_entries_for_message {
-        if let Err(err) = ctx
-            .http
-            .delete_message(
-                ChannelId::new(message_starboard_entry.starboard_channel_id.try_into()?),
-                MessageId::new(message_starboard_entry.starboard_message_id.try_into()?),
-                None,
-            )
-            .await
-        {
-            warn!(
-                starboard_message_id = message_starboard_entry.starboard_message_id,
-                "Failed to delete starboard message on reaction remove all: {err:?}"
-            );
+    let message = match channel_id
+        .message(&ctx.http, *removed_from_message_id)
+        .await
+    {
+        Ok(message) => message,
+        Err(e) => {
+            error!("Failed to get message from react remove all event: {:?}", e);
+            return Ok(());
         }
-        query!(
-            "DELETE FROM starred_messages WHERE starboard_message_id = ?1",
-            message_starboard_entry.starboard_message_id
+    };
+    for message_starboard_entry in starboard_entries_for_message {
+        reconcile_entry(
+            ctx,
+            data,
+            message_starboard_entry.starboard_channel_id,
+            &message,
+            false,
         )
-        .execute(data.database.pool())
         .await?;
     }
 
@@ -0,0 +1,66 @@
+use anyhow::{Context as _, Result};
+use serenity::all::{
+    Channel, ChannelId, ChannelType, Context, GuildId, PermissionOverwriteType, Permissions, RoleId,
+};
+
+/// Whether the given role can view a channel, using the parent channel for public threads.
+///
+/// Private threads are only visible to their members, so they are never treated as visible to a role.
+///
+/// The role's permissions are combined with `@everyone` the same way they would be for a member
+/// that has no other roles.
+pub async fn role_can_view_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    role_id: RoleId,
+    channel_id: ChannelId,
+) -> Result<bool> {
+    let Channel::Guild(mut channel) = channel_id.to_channel(ctx).await? else {
+        return Ok(false);
+    };
+    if channel.kind == ChannelType::PrivateThread {
+        return Ok(false);
+    }
+    if channel.thread_metadata.is_some() {
+        let parent_id = channel.parent_id.context("thread has no parent channel")?;
+        channel = match parent_id.to_channel(ctx).await? {
+            Channel::Guild(channel) => channel,
+            _ => return Ok(false),
+        };
+    }
+
+    // The @everyone role shares its id with the guild.
+    let everyone_role_id = RoleId::new(guild_id.get());
+    let role_permissions = match ctx.cache.guild(guild_id).map(|guild| {
+        let permissions_of = |id| guild.roles.get(&id).map(|r| r.permissions);
+        (permissions_of(everyone_role_id), permissions_of(role_id))
+    }) {
+        Some(permissions) => permissions,
+        None => {
+            let roles = guild_id.roles(ctx).await?;
+            let permissions_of = |id| roles.get(&id).map(|r| r.permissions);
+            (permissions_of(everyone_role_id), permissions_of(role_id))
+        }
+    };
+    let mut permissions = match role_permissions {
+        (Some(everyone), Some(role)) => everyone | role,
+        (Some(everyone), None) => everyone,
+        _ => return Ok(false),
+    };
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        return Ok(true);
+    }
+
+    // Apply the @everyone overwrite first and the role overwrite after it.
+    for target in [everyone_role_id, role_id] {
+        if let Some(overwrite) = channel
+            .permission_overwrites
+            .iter()
+            .find(|o| o.kind == PermissionOverwriteType::Role(target))
+        {
+            permissions = (permissions & !overwrite.deny) | overwrite.allow;
+        }
+    }
+
+    Ok(permissions.contains(Permissions::VIEW_CHANNEL))
+}
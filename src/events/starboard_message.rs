@@ -0,0 +1,171 @@
+use crate::emoji::StarboardEmoji;
+use linkify::LinkFinder;
+use serenity::all::{Colour, CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, Message};
+use url::Url;
+
+pub struct StarboardMessageParts {
+    pub content: String,
+    pub embed: CreateEmbed,
+}
+
+/// Get the components needed to create a starboard message.
+pub fn make_starboard_message(
+    original_message: &Message,
+    emoji: &StarboardEmoji,
+    react_count: usize,
+    veto_count: usize,
+    reacts_needed: usize,
+    mark_edited: bool,
+) -> StarboardMessageParts {
+    StarboardMessageParts {
+        embed: make_starboard_embed(
+            original_message,
+            emoji,
+            react_count,
+            veto_count,
+            reacts_needed,
+            mark_edited,
+        ),
+        content: original_message.link(),
+    }
+}
+
+/// Creates an embed for a starboard message.
+fn make_starboard_embed(
+    message: &Message,
+    emoji: &StarboardEmoji,
+    react_count: usize,
+    veto_count: usize,
+    reacts_needed: usize,
+    mark_edited: bool,
+) -> CreateEmbed {
+    let mut footer_text = match veto_count {
+        0 => format!("{}  • {}", react_count, message.id),
+        veto_count => format!("{} (-{})  • {}", react_count, veto_count, message.id),
+    };
+    if mark_edited && message.edited_timestamp.is_some() {
+        footer_text.push_str(" • (edited)");
+    }
+
+    // Custom emojis can't be rendered inside of footer text, so they are shown as the footer icon instead.
+    let footer = match emoji.image_url() {
+        Some(url) => CreateEmbedFooter::new(footer_text).icon_url(url),
+        None => CreateEmbedFooter::new(format!("{} {}", emoji, footer_text)),
+    };
+    let mut embed = CreateEmbed::default()
+        .author(CreateEmbedAuthor::new(&message.author.name).icon_url(message.author.face()))
+        .footer(footer)
+        .timestamp(message.timestamp)
+        .colour(match react_count {
+            count if count < reacts_needed * 2 => Colour::DARK_ORANGE, // between minimum and 2x
+            count if count < reacts_needed * 3 => Colour::ORANGE, // between 2x to 3x of minimum,
+            _ => Colour::GOLD,                                    // 3x or higher of minimum
+        });
+
+    // Add message content
+    if !message.content.is_empty() {
+        embed = embed.description(&message.content);
+    }
+
+    // Add the first attachment as an image or add a field with all attachments.
+    if let Some(attachment) = message.attachments.first() {
+        let content_type = attachment.content_type.as_deref().unwrap_or("");
+        embed = if content_type.starts_with("image") {
+            embed.image(&attachment.url)
+        } else {
+            embed.field(
+                "Attachments",
+                message
+                    .attachments
+                    .iter()
+                    .map(|a| format!("[{}]({})", a.filename, a.url))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                false,
+            )
+        };
+    }
+    // Embed the first image URL found in the message content.
+    else if let Some(link) =
+        find_all_image_urls_in_str(&message.content).and_then(|links| links.into_iter().next())
+    {
+        embed = embed.image(link.to_string());
+    }
+    // Embed the first image from an embed on the message.
+    else if let Some(image_url) = message
+        .embeds
+        .iter()
+        .find_map(|e| e.image.as_ref().map(|img| &img.url))
+    {
+        embed = embed.image(image_url);
+    }
+
+    // Add reply context
+    if let Some(reply) = &message.referenced_message {
+        const MAX_REPLY_CHARS: usize = 524;
+
+        let reply_content = if reply.content.is_empty() {
+            "*sent an attachment, embed or sticker.*".to_string()
+        } else if reply.content.chars().count() > MAX_REPLY_CHARS {
+            reply
+                .content
+                .chars()
+                .take(MAX_REPLY_CHARS)
+                .chain("...".chars())
+                .collect::<String>()
+        } else {
+            reply.content.clone()
+        };
+
+        embed = embed.field(
+            format!("In reply to {}'s message", reply.author.name),
+            reply_content,
+            false,
+        );
+    }
+
+    embed
+}
+
+/// Returns a vec with all complete image links inside of the given string.
+///
+/// File types are checked case-insensitively.
+///
+/// ## Media Support
+/// Media support makes a best effort to mirror the formats that Discord can use inside of an image field in an embed.
+///
+/// * `jpg`
+/// * `jpeg`
+/// * `png`
+/// * `gif`
+fn find_all_image_urls_in_str(s: &str) -> Option<Vec<Url>> {
+    if s.is_empty() {
+        return None;
+    }
+
+    let links: Vec<_> = LinkFinder::new().links(s).collect();
+    if links.is_empty() {
+        return None;
+    }
+
+    let image_urls: Vec<Url> = links
+        .iter()
+        .filter_map(|link| {
+            let link_str = link.as_str().to_lowercase();
+            if !link_str.contains(".jpg")
+                && !link_str.contains(".jpeg")
+                && !link_str.contains(".png")
+                && !link_str.contains(".gif")
+            {
+                return None;
+            }
+            Url::parse(link.as_str()).ok()
+        })
+        .collect();
+
+    if image_urls.is_empty() {
+        None
+    } else {
+        Some(image_urls)
+    }
+}
@@ -1,22 +1,55 @@
 mod commands;
+mod config_cache;
 mod database;
+mod delete_policy;
+mod emoji;
+mod entry_locks;
 mod events;
+mod reaction_debouncer;
+mod starboard_purge;
 
 use crate::events::event_handler;
-use crate::{commands::starboard_settings_sub, database::Database};
+use crate::{
+    commands::{
+        starboard_context_menu, starboard_diagnose_context_menu, starboard_settings_sub,
+        starboard_sub,
+    },
+    config_cache::ConfigCache,
+    database::Database,
+    entry_locks::EntryLocks,
+    reaction_debouncer::ReactionDebouncer,
+    starboard_purge::purge_deleted_starboards,
+};
 use anyhow::{Context, Error, Result};
 use clap::Parser;
 use dotenvy::dotenv;
 use poise::serenity_prelude::{
     ActivityData, ClientBuilder, CreateAllowedMentions, GatewayIntents, OnlineStatus,
 };
+use std::time::Duration;
 use tokio::signal;
+use tracing::error;
 use tracing_subscriber::EnvFilter;
 
 type PoiseContext<'a> = poise::Context<'a, AppState, Error>;
 
 struct AppState {
     database: Database,
+    config_cache: ConfigCache,
+    entry_locks: EntryLocks,
+    reaction_debouncer: ReactionDebouncer,
+}
+
+/// Forget the cached starboards of the guild after a settings command, so changes are seen by the next reaction.
+fn invalidate_config_cache(ctx: PoiseContext<'_>) {
+    if ctx
+        .command()
+        .qualified_name
+        .starts_with("starboard-settings")
+        && let Some(guild_id) = ctx.guild_id()
+    {
+        ctx.data().config_cache.invalidate(guild_id);
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -38,6 +71,14 @@ struct AppSettings {
     /// The custom status to use for the bot's profile
     #[clap(long = "discord-bot-status", env = "ACCORD_DISCORD_BOT_STATUS")]
     discord_bot_status: Option<Box<str>>,
+
+    /// How long to wait for more reactions on a message before updating starboards, in milliseconds.
+    #[clap(
+        long = "reaction-debounce-ms",
+        env = "ACCORD_REACTION_DEBOUNCE_MS",
+        default_value_t = 1500
+    )]
+    reaction_debounce_ms: u64,
 }
 
 #[tokio::main]
@@ -51,9 +92,27 @@ async fn main() -> Result<()> {
     let database = Database::new(&args.database_url)
         .await
         .context("failed to initialise database")?;
+    tokio::spawn(purge_deleted_starboards(database.pool().clone()));
     let framework = poise::Framework::<AppState, Error>::builder()
         .options(poise::FrameworkOptions {
-            commands: vec![starboard_settings_sub()],
+            commands: vec![
+                starboard_settings_sub(),
+                starboard_sub(),
+                starboard_context_menu(),
+                starboard_diagnose_context_menu(),
+            ],
+            post_command: |ctx| Box::pin(async move { invalidate_config_cache(ctx) }),
+            on_error: |error| {
+                Box::pin(async move {
+                    // Settings commands can fail after they have already changed something.
+                    if let Some(ctx) = error.ctx() {
+                        invalidate_config_cache(ctx);
+                    }
+                    if let Err(err) = poise::builtins::on_error(error).await {
+                        error!("Error while handling error: {err}");
+                    }
+                })
+            },
             event_handler: |ctx, event, framework, data| {
                 Box::pin(event_handler(ctx, event, framework, data))
             },
@@ -93,7 +152,14 @@ async fn main() -> Result<()> {
                     );
                 }
 
-                Ok(AppState { database })
+                Ok(AppState {
+                    database,
+                    config_cache: ConfigCache::default(),
+                    entry_locks: EntryLocks::default(),
+                    reaction_debouncer: ReactionDebouncer::new(Duration::from_millis(
+                        args.reaction_debounce_ms,
+                    )),
+                })
             })
         })
         .build();
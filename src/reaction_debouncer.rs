@@ -0,0 +1,54 @@
+use crate::emoji::StarboardEmoji;
+use poise::serenity_prelude::MessageId;
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+use tokio::time::sleep;
+
+/// Coalesces bursts of reaction events on the same message into a single update.
+///
+/// The first event for a message waits out the window and then handles every emoji reacted with in the
+/// meantime, so popular messages are only counted and edited once per window instead of once per reaction.
+#[derive(Debug)]
+pub struct ReactionDebouncer {
+    window: Duration,
+    pending: Mutex<HashMap<MessageId, Vec<StarboardEmoji>>>,
+}
+
+impl ReactionDebouncer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: Mutex::default(),
+        }
+    }
+
+    /// Record a reaction event for a message.
+    ///
+    /// Returns the emojis to handle once the window has passed, or `None` if an earlier event for the
+    /// same message is already waiting and will handle this one too.
+    pub async fn coalesce(
+        &self,
+        message_id: MessageId,
+        emoji: StarboardEmoji,
+    ) -> Option<Vec<StarboardEmoji>> {
+        if self.window.is_zero() {
+            return Some(vec![emoji]);
+        }
+
+        {
+            let mut pending = self.pending.lock().expect("reaction debouncer poisoned");
+            if let Some(emojis) = pending.get_mut(&message_id) {
+                if !emojis.contains(&emoji) {
+                    emojis.push(emoji);
+                }
+                return None;
+            }
+            pending.insert(message_id, vec![emoji]);
+        }
+
+        sleep(self.window).await;
+        self.pending
+            .lock()
+            .expect("reaction debouncer poisoned")
+            .remove(&message_id)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::database::DatabasePool;
+use poise::serenity_prelude::Timestamp;
+use sqlx::query;
+use std::time::Duration;
+use tokio::time::interval;
+use tracing::{error, info};
+
+/// How long a deleted starboard can be restored for before it is purged, in seconds.
+pub const RESTORE_WINDOW: i64 = 60 * 60 * 24 * 7;
+
+/// How often deleted starboards are checked for purging.
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Permanently remove deleted starboards and their entries once they can no longer be restored.
+pub async fn purge_deleted_starboards(pool: DatabasePool) {
+    let mut interval = interval(PURGE_INTERVAL);
+    loop {
+        interval.tick().await;
+        let purge_before = Timestamp::now().unix_timestamp() - RESTORE_WINDOW;
+        match query!(
+            "DELETE FROM starboards WHERE deleted_at IS NOT NULL AND deleted_at <= ?1",
+            purge_before
+        )
+        .execute(&pool)
+        .await
+        {
+            Ok(result) if result.rows_affected() > 0 => {
+                info!("Purged {} deleted starboards", result.rows_affected());
+            }
+            Ok(_) => {}
+            Err(err) => error!("Failed to purge deleted starboards: {err:?}"),
+        }
+    }
+}